@@ -14,7 +14,7 @@ where
         let mut last = 0;
 
         for i in 0..line.len() {
-            if let Some(digit) = digit_map(line[i..].as_bytes()) {
+            if let Some(digit) = digit_map(&line.as_bytes()[i..]) {
                 if first == 0 {
                     first = digit;
                     last = digit;
@@ -1,8 +1,8 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse_data);
 
 use advent_of_code::maneatingape::parse::*;
 
-struct Game {
+pub struct Game {
     id: u32,
     sets: Vec<Set>,
 }
@@ -46,11 +46,9 @@ fn parse_data(input: &str) -> Vec<Game> {
     input.lines().map(Game::from).collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let data = parse_data(input);
-
+pub fn part_one(data: &[Game]) -> Option<u32> {
     let result = data
-        .into_iter()
+        .iter()
         .filter(|game| {
             !game
                 .sets
@@ -63,11 +61,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let data = parse_data(input);
-
+pub fn part_two(data: &[Game]) -> Option<u32> {
     let result = data
-        .into_iter()
+        .iter()
         .map(|game| Set {
             red: game.sets.iter().map(|x| x.red).max().unwrap(),
             green: game.sets.iter().map(|x| x.green).max().unwrap(),
@@ -85,13 +81,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_data(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_data(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(2286));
     }
 }
@@ -19,7 +19,7 @@ fn parse_data(input: &str) -> (Vec<Number>, Symbols) {
     for (y, line) in input.lines().enumerate() {
         let mut i = 0;
         while i < line.len() {
-            if line[i..].as_bytes()[0].is_ascii_digit() {
+            if line.as_bytes()[i].is_ascii_digit() {
                 let value = (&line[i..]).unsigned::<u32>();
                 let value_len = value.ilog10() as usize + 1;
 
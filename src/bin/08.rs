@@ -55,13 +55,9 @@ where
 pub fn part_one(input: &str) -> Option<u64> {
     let state = parse_data(input);
 
-    let location = state
-        .graph
-        .keys()
-        .find(|loc| loc == &&[b'A', b'A', b'A'])
-        .unwrap();
+    let location = state.graph.keys().find(|loc| loc == &b"AAA").unwrap();
 
-    let result = part_x(&state, location, |loc| loc == &[b'Z', b'Z', b'Z']);
+    let result = part_x(&state, location, |loc| loc == b"ZZZ");
 
     Some(result)
 }
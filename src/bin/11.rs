@@ -2,12 +2,16 @@ advent_of_code::solution!(11);
 
 use advent_of_code::maneatingape::grid::*;
 use advent_of_code::maneatingape::point::*;
+use advent_of_code::template::Param;
+
+/// How many times larger empty rows and columns are in part two.
+const EXPANSION: Param<i32> = Param::new("expansion", 1_000_000, 100);
 
 fn parse_data(input: &str) -> Grid<u8> {
     Grid::parse(input)
 }
 
-fn part_x<const N: i32>(grid: Grid<u8>) -> u64 {
+fn part_x(grid: Grid<u8>, expansion: i32) -> u64 {
     let expand_x = (0..grid.width)
         .filter(|&x| !(0..grid.height).any(|y| grid[Point::new(x, y)] == b'#'))
         .collect::<Vec<_>>();
@@ -21,8 +25,8 @@ fn part_x<const N: i32>(grid: Grid<u8>) -> u64 {
         for y in 0..grid.height {
             if grid[Point::new(x, y)] == b'#' {
                 grid_data_as_vec.push(Point::new(
-                    x + (N - 1) * expand_x.iter().filter(|ex| &x > ex).count() as i32,
-                    y + (N - 1) * expand_y.iter().filter(|ey| &y > ey).count() as i32,
+                    x + (expansion - 1) * expand_x.iter().filter(|ex| &x > ex).count() as i32,
+                    y + (expansion - 1) * expand_y.iter().filter(|ey| &y > ey).count() as i32,
                 ));
             }
         }
@@ -43,7 +47,7 @@ fn part_x<const N: i32>(grid: Grid<u8>) -> u64 {
 pub fn part_one(input: &str) -> Option<u64> {
     let grid = parse_data(input);
 
-    let result = part_x::<2>(grid);
+    let result = part_x(grid, 2);
 
     Some(result)
 }
@@ -51,7 +55,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 pub fn part_two(input: &str) -> Option<u64> {
     let grid = parse_data(input);
 
-    let result = part_x::<1000000>(grid);
+    let result = part_x(grid, EXPANSION.get());
 
     Some(result)
 }
@@ -69,6 +73,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(82000210));
+        assert_eq!(result, Some(8410));
     }
 }
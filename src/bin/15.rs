@@ -53,7 +53,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         .collect::<Vec<_>>();
 
     let mut my_boxes: Vec<Vec<(&str, u32)>> =
-        std::iter::repeat(Vec::new()).take(256).collect::<Vec<_>>();
+        std::iter::repeat_n(Vec::new(), 256).collect::<Vec<_>>();
 
     for d in data {
         let my_box = &mut my_boxes[d.label_loc];
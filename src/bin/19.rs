@@ -120,9 +120,9 @@ fn get_final_node_value(node: &TreeNode, rating: &[u32]) -> NodeValue {
         };
 
         if should_go_left {
-            return get_final_node_value(node.left.as_ref().unwrap(), rating);
+            get_final_node_value(node.left.as_ref().unwrap(), rating)
         } else {
-            return get_final_node_value(node.right.as_ref().unwrap(), rating);
+            get_final_node_value(node.right.as_ref().unwrap(), rating)
         }
     }
 }
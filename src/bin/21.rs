@@ -2,6 +2,10 @@ advent_of_code::solution!(21);
 
 use advent_of_code::maneatingape::grid::*;
 use advent_of_code::maneatingape::point::*;
+use advent_of_code::template::Param;
+
+/// The number of steps to walk in part one.
+const STEPS: Param<usize> = Param::new("steps", 64, 6);
 
 type UBig = advent_of_code::majcn::bignumbers::UX64<13>;
 
@@ -75,7 +79,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut my_positions_bits = vec![UBig::ZERO; rocks_bits.len()];
     my_positions_bits[(len_y + 2) / 2] |= UBig::ONE << ((len_x + 2) / 2);
 
-    let result = part_x([64], my_positions_bits, rocks_bits)
+    let result = part_x([STEPS.get()], my_positions_bits, rocks_bits)
         .into_iter()
         .next()
         .map(|x| x.1 as u64)
@@ -148,8 +152,10 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(47));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ));
+        assert_eq!(result, Some(16));
     }
 
     #[test]
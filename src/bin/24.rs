@@ -2,6 +2,11 @@ advent_of_code::solution!(24);
 
 use advent_of_code::maneatingape::iter::*;
 use advent_of_code::maneatingape::parse::*;
+use advent_of_code::template::Param;
+
+/// Bounds of the test area that paths have to cross in, on both the X and Y axis.
+const TEST_AREA_MIN: Param<i64> = Param::new("test_area_min", 200000000000000, 7);
+const TEST_AREA_MAX: Param<i64> = Param::new("test_area_max", 400000000000000, 27);
 
 struct Hailstone {
     x: i64,
@@ -36,35 +41,38 @@ fn parse_data(input: &str) -> Vec<Hailstone> {
 pub fn part_one(input: &str) -> Option<u32> {
     let data = parse_data(input);
 
+    let (min, max) = (TEST_AREA_MIN.get() as i128, TEST_AREA_MAX.get() as i128);
+
     let mut result = 0;
     for i in 0..data.len() {
         for j in (i + 1)..data.len() {
-            let x1 = data[i].x;
-            let x2 = data[i].x + data[i].vx;
-            let x3 = data[j].x;
-            let x4 = data[j].x + data[j].vx;
+            let x1 = data[i].x as i128;
+            let x2 = (data[i].x + data[i].vx) as i128;
+            let x3 = data[j].x as i128;
+            let x4 = (data[j].x + data[j].vx) as i128;
 
-            let y1 = data[i].y;
-            let y2 = data[i].y + data[i].vy;
-            let y3 = data[j].y;
-            let y4 = data[j].y + data[j].vy;
+            let y1 = data[i].y as i128;
+            let y2 = (data[i].y + data[i].vy) as i128;
+            let y3 = data[j].y as i128;
+            let y4 = (data[j].y + data[j].vy) as i128;
 
             let det = (x1 - x2) * (y3 - y4) - (y1 - y2) * (x3 - x4);
             if det == 0 {
                 continue;
             }
 
-            let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) / det;
-            let u = ((x1 - x3) * (y1 - y2) - (y1 - y3) * (x1 - x2)) / det;
+            // keep `t` and `u` as fractions over a positive `det`, rounding them skews small examples.
+            let sign = det.signum();
+            let det = det * sign;
+            let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) * sign;
+            let u = ((x1 - x3) * (y1 - y2) - (y1 - y3) * (x1 - x2)) * sign;
+
+            let x = x1 * det + t * (x2 - x1);
+            let y = y1 * det + t * (y2 - y1);
 
-            let x = x1 + t * (x2 - x1);
-            let y = y1 + t * (y2 - y1);
+            let test_area = min * det..=max * det;
 
-            if (200000000000000..=400000000000000).contains(&x)
-                && (200000000000000..=400000000000000).contains(&y)
-                && t > 0
-                && u > 0
-            {
+            if test_area.contains(&x) && test_area.contains(&y) && t > 0 && u > 0 {
                 result += 1;
             }
         }
@@ -73,6 +81,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
+#[allow(clippy::needless_range_loop)]
 fn gaussian_elimination<const N: usize>(
     coefficients: &mut [[f64; N]; N],
     rhs: &mut [f64; N],
@@ -151,7 +160,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
+        assert_eq!(result, Some(2));
     }
 
     #[test]
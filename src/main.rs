@@ -1,42 +1,186 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::time::TimeOptions;
+use advent_of_code::template::commands::{
+    all, download, examples, history, read, scaffold, solve, status, time, verify, watch,
+};
 use args::{parse, AppArguments};
+use solutions::SOLUTIONS;
 
+#[cfg(feature = "today")]
+use advent_of_code::template::commands::wait;
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+// NOTE: the per-day test suites and the dhat allocator only belong into the per-day binaries.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod solutions;
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod solutions {
+    pub const SOLUTIONS: &[advent_of_code::template::Solution] = &[];
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::runner::parse_timeout;
+    use advent_of_code::template::{
+        all_years, output::OutputFormat, reports::ReportFormat, Day, Year,
+    };
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
+            day: Day,
+        },
+        Examples {
+            year: Year,
             day: Day,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: OutputFormat,
+            timeout: Option<Duration>,
+            watch: bool,
+            input: Option<String>,
         },
         All {
+            years: Vec<Year>,
             release: bool,
+            spawn: bool,
+            format: OutputFormat,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         Time {
+            years: Vec<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            spawn: bool,
+            format: OutputFormat,
+            median: bool,
+            compare: bool,
+            threshold: f64,
+            baseline: Option<String>,
+            save_baseline: Option<String>,
+            jobs: usize,
+            timeout: Option<Duration>,
+            memory: bool,
+            reports: Vec<ReportFormat>,
+        },
+        History {
+            years: Vec<Year>,
+            day: Option<Day>,
+        },
+        Status {
+            years: Vec<Year>,
+            run_tests: bool,
+        },
+        Verify {
+            years: Vec<Year>,
+            spawn: bool,
+            record: bool,
+            format: OutputFormat,
+            jobs: usize,
+            timeout: Option<Duration>,
+        },
+        #[cfg(feature = "today")]
+        Today {
+            year: Year,
         },
         #[cfg(feature = "today")]
-        Today,
+        Wait {
+            year: Year,
+        },
+    }
+
+    /// Parse the year passed with `--year`, defaulting to the primary year set in `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Ok(Year::primary().unwrap_or_else(|| {
+                eprintln!(
+                    "No year specified. Pass `--year` or set AOC_YEAR in .cargo/config.toml."
+                );
+                process::exit(1);
+            })),
+        }
+    }
+
+    /// Parse the year passed with `--year`, and a day of its event.
+    fn parse_year_and_day(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Year, Day), pico_args::Error> {
+        let year = parse_year(args)?;
+        let day: String = args.free_from_str()?;
+        let day =
+            Day::parse_in(year, &day).map_err(|e| pico_args::Error::Utf8ArgumentParsingFailed {
+                value: day.clone(),
+                cause: e.to_string(),
+            })?;
+        Ok((year, day))
+    }
+
+    /// Parse an optional day, which has to be a day of the event of at least one of `years`.
+    fn parse_opt_day(
+        args: &mut pico_args::Arguments,
+        years: &[Year],
+    ) -> Result<Option<Day>, pico_args::Error> {
+        let Some(day) = args.opt_free_from_str::<String>()? else {
+            return Ok(None);
+        };
+
+        let mut error = None;
+        for year in years {
+            match Day::parse_in(*year, &day) {
+                Ok(day) => return Ok(Some(day)),
+                Err(e) => error = Some(e),
+            }
+        }
+
+        Err(pico_args::Error::Utf8ArgumentParsingFailed {
+            value: day,
+            cause: error.map(|e| e.to_string()).unwrap_or_default(),
+        })
+    }
+
+    /// Parse the years passed with one or more `--year` flags, or with `--all-years`.
+    /// Defaults to the primary year set in `AOC_YEAR`.
+    fn parse_years(args: &mut pico_args::Arguments) -> Result<Vec<Year>, pico_args::Error> {
+        if args.contains("--all-years") {
+            return Ok(all_years());
+        }
+
+        let mut years: Vec<Year> = args.values_from_str("--year")?;
+
+        if years.is_empty() {
+            years.push(parse_year(args)?);
+        }
+
+        years.sort_unstable();
+        years.dedup();
+        Ok(years)
+    }
+
+    /// Parse the number of days to run concurrently, passed with `--jobs`. Defaults to running days one by one.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, pico_args::Error> {
+        let jobs: usize = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+        Ok(jobs.max(1))
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -44,36 +188,123 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                years: parse_years(&mut args)?,
                 release: args.contains("--release"),
+                spawn: args.contains("--spawn"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                jobs: parse_jobs(&mut args)?,
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let spawn = args.contains("--spawn");
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let median = args.contains("--median");
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let baseline = args.opt_value_from_str("--baseline")?;
+                let save_baseline = args.opt_value_from_str("--save-baseline")?;
+                let jobs = parse_jobs(&mut args)?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_timeout)?;
+                let memory = args.contains("--memory");
+                let reports = args.values_from_str("--report")?;
+                let years = parse_years(&mut args)?;
 
                 AppArguments::Time {
+                    day: parse_opt_day(&mut args, &years)?,
+                    years,
                     all,
-                    day: args.opt_free_from_str()?,
                     store,
+                    spawn,
+                    format,
+                    median,
+                    compare,
+                    threshold,
+                    baseline,
+                    save_baseline,
+                    jobs,
+                    timeout,
+                    memory,
+                    reports,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("history") => {
+                let years = parse_years(&mut args)?;
+                AppArguments::History {
+                    day: parse_opt_day(&mut args, &years)?,
+                    years,
+                }
+            }
+            Some("status") => AppArguments::Status {
+                years: parse_years(&mut args)?,
+                run_tests: !args.contains("--no-tests"),
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
+            Some("verify") => AppArguments::Verify {
+                years: parse_years(&mut args)?,
+                spawn: args.contains("--spawn"),
+                record: args.contains("--record"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                jobs: parse_jobs(&mut args)?,
+                timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("download") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                AppArguments::Download { year, day }
+            }
+            Some("read") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                AppArguments::Read { year, day }
+            }
+            Some("examples") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                AppArguments::Examples { year, day }
+            }
+            Some("scaffold") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                AppArguments::Scaffold {
+                    year,
+                    day,
+                    download: args.contains("--download"),
+                }
+            }
+            Some("solve") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                let solve = AppArguments::Solve {
+                    year,
+                    day,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    timeout: args.opt_value_from_fn("--timeout", parse_timeout)?,
+                    watch: args.contains("--watch"),
+                    input: args.opt_value_from_str("--input")?,
+                };
+
+                if let AppArguments::Solve {
+                    submit,
+                    watch,
+                    input: Some(_),
+                    ..
+                } = &solve
+                {
+                    if submit.is_some() || *watch {
+                        eprintln!("`--input` can not be combined with `--submit` or `--watch`.");
+                        process::exit(1);
+                    }
+                }
+
+                solve
+            }
+            #[cfg(feature = "today")]
+            Some("today") => AppArguments::Today {
+                year: parse_year(&mut args)?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("wait") => AppArguments::Wait {
+                year: parse_year(&mut args)?,
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -100,34 +331,107 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                years,
+                release,
+                spawn,
+                format,
+                jobs,
+                timeout,
+            } => all::handle(SOLUTIONS, &years, release, spawn, format, jobs, timeout),
+            AppArguments::Time {
+                years,
+                day,
+                all,
+                store,
+                spawn,
+                format,
+                median,
+                compare,
+                threshold,
+                baseline,
+                save_baseline,
+                jobs,
+                timeout,
+                memory,
+                reports,
+            } => time::handle(
+                SOLUTIONS,
+                &years,
+                TimeOptions {
+                    day,
+                    run_all: all,
+                    store,
+                    spawn,
+                    format,
+                    readme_median: median,
+                    compare,
+                    threshold_pct: threshold,
+                    baseline,
+                    save_baseline,
+                    jobs,
+                    timeout,
+                    memory,
+                    reports,
+                },
+            ),
+            AppArguments::History { years, day } => history::handle(&years, day),
+            AppArguments::Status { years, run_tests } => {
+                status::handle(SOLUTIONS, &years, run_tests)
+            }
+            AppArguments::Verify {
+                years,
+                spawn,
+                record,
+                format,
+                jobs,
+                timeout,
+            } => verify::handle(SOLUTIONS, &years, spawn, record, format, jobs, timeout),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Examples { year, day } => examples::handle(year, day),
+            AppArguments::Scaffold {
+                year,
+                day,
+                download,
+            } => {
+                scaffold::handle(year, day);
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
                 }
             }
             AppArguments::Solve {
+                year,
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                format,
+                timeout,
+                watch,
+                input,
+            } => {
+                if watch {
+                    watch::handle(year, day, release, timeout);
+                } else {
+                    solve::handle(year, day, release, dhat, submit, format, timeout, input);
+                }
+            }
+            #[cfg(feature = "today")]
+            AppArguments::Wait { year } => wait::handle(year),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
+            AppArguments::Today { year } => {
+                match Day::today(year) {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(year, day);
+                        download::handle(year, day);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the {} days of the {year} event in december. \
+                            Please use `scaffold` with a specific day.",
+                            year.days()
                         );
                         process::exit(1)
                     }
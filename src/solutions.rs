@@ -0,0 +1,82 @@
+//! Registry of the solutions that are compiled into the main binary.
+//! `all` and `time` use it to run solutions in-process. Days that are not registered here fall back to running their own binary.
+use advent_of_code::template::Solution;
+
+#[path = "bin/01.rs"]
+mod day01;
+#[path = "bin/02.rs"]
+mod day02;
+#[path = "bin/03.rs"]
+mod day03;
+#[path = "bin/04.rs"]
+mod day04;
+#[path = "bin/05.rs"]
+mod day05;
+#[path = "bin/06.rs"]
+mod day06;
+#[path = "bin/07.rs"]
+mod day07;
+#[path = "bin/08.rs"]
+mod day08;
+#[path = "bin/09.rs"]
+mod day09;
+#[path = "bin/10.rs"]
+mod day10;
+#[path = "bin/11.rs"]
+mod day11;
+#[path = "bin/12.rs"]
+mod day12;
+#[path = "bin/13.rs"]
+mod day13;
+#[path = "bin/14.rs"]
+mod day14;
+#[path = "bin/15.rs"]
+mod day15;
+#[path = "bin/16.rs"]
+mod day16;
+#[path = "bin/17.rs"]
+mod day17;
+#[path = "bin/18.rs"]
+mod day18;
+#[path = "bin/19.rs"]
+mod day19;
+#[path = "bin/20.rs"]
+mod day20;
+#[path = "bin/21.rs"]
+mod day21;
+#[path = "bin/22.rs"]
+mod day22;
+#[path = "bin/23.rs"]
+mod day23;
+#[path = "bin/24.rs"]
+mod day24;
+#[path = "bin/25.rs"]
+mod day25;
+
+pub const SOLUTIONS: &[Solution] = &[
+    day01::SOLUTION,
+    day02::SOLUTION,
+    day03::SOLUTION,
+    day04::SOLUTION,
+    day05::SOLUTION,
+    day06::SOLUTION,
+    day07::SOLUTION,
+    day08::SOLUTION,
+    day09::SOLUTION,
+    day10::SOLUTION,
+    day11::SOLUTION,
+    day12::SOLUTION,
+    day13::SOLUTION,
+    day14::SOLUTION,
+    day15::SOLUTION,
+    day16::SOLUTION,
+    day17::SOLUTION,
+    day18::SOLUTION,
+    day19::SOLUTION,
+    day20::SOLUTION,
+    day21::SOLUTION,
+    day22::SOLUTION,
+    day23::SOLUTION,
+    day24::SOLUTION,
+    day25::SOLUTION,
+];
@@ -0,0 +1,262 @@
+/// Module that stores the known correct answers of each day, and verifies results against them.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+/// The folder that holds the known correct answers of a year, e.g. `data/answers`.
+pub fn get_answers_dir(year: Year) -> PathBuf {
+    year.data_dir().join("answers")
+}
+
+/// The known correct answers of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Rehydrate the answers of a day from its JSON file. If not present, returns empty answers.
+    pub fn read(year: Year, day: Day) -> Self {
+        Self::read_in(&get_answers_dir(year), day)
+    }
+
+    /// Same as [`Answers::read`], but reads from the JSON file in `dir`.
+    pub fn read_in(dir: &Path, day: Day) -> Self {
+        let s = match fs::read_to_string(get_answers_path(dir, day)) {
+            Ok(s) => s,
+            Err(_) => return Self::default(),
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Could not read answers for day {day}: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Dehydrate the answers of a day to its JSON file.
+    pub fn store(&self, year: Year, day: Day) -> Result<(), Error> {
+        self.store_in(&get_answers_dir(year), day)
+    }
+
+    /// Same as [`Answers::store`], but writes to the JSON file in `dir`.
+    pub fn store_in(&self, dir: &Path, day: Day) -> Result<(), Error> {
+        fs::create_dir_all(dir)?;
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_answers_path(dir, day))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_1 = Some(answer.into()),
+            2 => self.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Check an answer against the known correct answer of a part.
+    pub fn verify(&self, part: u8, answer: Option<&str>) -> Verdict {
+        match (self.get(part), answer) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => Verdict::Correct,
+            (Some(_), _) => Verdict::Wrong,
+        }
+    }
+}
+
+fn get_answers_path(dir: &Path, day: Day) -> PathBuf {
+    dir.join(format!("{day}.json"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Whether an answer matches the known correct answer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    /// There is no known correct answer to compare against.
+    #[default]
+    Unknown,
+}
+
+impl Verdict {
+    /// Symbol that is printed next to an answer.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Verdict::Correct => "✔",
+            Verdict::Wrong => "✖",
+            Verdict::Unknown => "?",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::Wrong => f.write_str("wrong"),
+            Verdict::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: answers may be written by hand, so accept both strings and numbers.
+        let get_answer = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(x)) => Ok(Some(x.clone())),
+            Some(JsonValue::Number(x)) => number_to_answer(*x, key).map(Some),
+            Some(_) => Err(format!(
+                "expected `{key}` to be null, a string or a number."
+            )),
+        };
+
+        Ok(Answers {
+            part_1: get_answer("part_1")?,
+            part_2: get_answer("part_2")?,
+        })
+    }
+}
+
+/// The largest integer such that it and every integer below it are represented exactly by an [`f64`],
+/// i.e. `2^53 - 1`.
+const MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Convert an answer that was written as a JSON number to a string.
+/// JSON numbers are read as [`f64`], which silently rounds large integers. Answers that are not integers,
+/// or that might have been rounded, are rejected, and have to be written as a string instead.
+pub(crate) fn number_to_answer(x: f64, key: &str) -> Result<String, String> {
+    if x.fract() == 0.0 && x.abs() <= MAX_EXACT_INTEGER {
+        Ok(x.to_string())
+    } else {
+        Err(format!(
+            "expected `{key}` to be an integer between -(2^53 - 1) and 2^53 - 1, write larger answers as a string, e.g. `\"{key}\": \"12345678901234567890\"`."
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verdict};
+
+    #[test]
+    fn verifies_answers() {
+        let answers = Answers {
+            part_1: Some("142".into()),
+            part_2: None,
+        };
+
+        assert_eq!(answers.verify(1, Some("142")), Verdict::Correct);
+        assert_eq!(answers.verify(1, Some("143")), Verdict::Wrong);
+        assert_eq!(answers.verify(1, None), Verdict::Wrong);
+        assert_eq!(answers.verify(2, Some("281")), Verdict::Unknown);
+        assert_eq!(answers.verify(2, None), Verdict::Unknown);
+    }
+
+    #[test]
+    fn parses_answers() {
+        let json = r#"{ "part_1": "142", "part_2": 281 }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(1), Some("142"));
+        assert_eq!(answers.get(2), Some("281"));
+    }
+
+    #[test]
+    fn rejects_inexact_numbers() {
+        let answers = Answers::try_from(
+            r#"{ "part_1": 9007199254740991, "part_2": "9007199254740993" }"#.to_string(),
+        )
+        .unwrap();
+        assert_eq!(answers.get(1), Some("9007199254740991"));
+        assert_eq!(answers.get(2), Some("9007199254740993"));
+
+        // parsed as a float, this would silently become 9007199254740992.
+        let error = Answers::try_from(r#"{ "part_1": 9007199254740993 }"#.to_string()).unwrap_err();
+        assert!(error.contains("as a string"));
+        assert!(Answers::try_from(r#"{ "part_1": 1.5 }"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = Answers::try_from(r#"{ "part_1": "142" }"#.to_string()).unwrap();
+        assert_eq!(answers.get(1), Some("142"));
+        assert_eq!(answers.get(2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "part_1": [] }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_verdicts() {
+        for verdict in [Verdict::Correct, Verdict::Wrong, Verdict::Unknown] {
+            assert_eq!(verdict.to_string().parse::<Verdict>().unwrap(), verdict);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+/// Built-in HTTP client for Advent of Code, used instead of aoc-cli when a session cookie is configured.
+use std::{env, fs, path::PathBuf};
+
+use crate::template::backend::{AocBackend, BackendError};
+use crate::template::{Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, user_agent: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().trim_start_matches("session=").into(),
+            agent: ureq::AgentBuilder::new().user_agent(user_agent).build(),
+        }
+    }
+
+    /// Configure a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, or from the session file that aoc-cli uses.
+    ///  2. the base URL is read from `AOC_BASE_URL`, and defaults to `https://adventofcode.com`.
+    ///  3. the user agent is read from `AOC_USER_AGENT`, see [`user_agent`].
+    pub fn from_env() -> Result<Self, BackendError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(read_session_file)
+            .filter(|x| !x.trim().is_empty())
+            .ok_or(BackendError::MissingSession)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        let user_agent = user_agent(env::var("AOC_USER_AGENT").ok().as_deref());
+
+        Ok(Self::new(&base_url, &session, &user_agent))
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, BackendError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| map_error(url, e))?;
+        Ok(response.into_string()?)
+    }
+}
+
+impl AocBackend for AocClient {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn fetch_input(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        self.get(&format!("{}/input", self.day_url(year, day)))
+    }
+
+    fn fetch_puzzle(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        let html = self.get(&self.day_url(year, day))?;
+        let articles = extract_articles(&html);
+
+        if articles.is_empty() {
+            return Err(BackendError::Http(
+                "puzzle page did not contain a description.".into(),
+            ));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|article| html_to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, BackendError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| map_error(&url, e))?;
+
+        let html = response.into_string()?;
+        Ok(extract_articles(&html)
+            .first()
+            .map_or(html.clone(), |article| html_to_markdown(article)))
+    }
+}
+
+fn map_error(url: &str, e: ureq::Error) -> BackendError {
+    match e {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            // plain-text error bodies explain what went wrong, e.g. that the puzzle is not unlocked yet.
+            let body = body.lines().next().unwrap_or_default().trim();
+            let body = if body.starts_with('<') { "" } else { body };
+            BackendError::Http(
+                format!("{url} responded with status {code}. {body}")
+                    .trim_end()
+                    .into(),
+            )
+        }
+        ureq::Error::Transport(e) => BackendError::Http(e.to_string()),
+    }
+}
+
+/// Advent of Code asks automated tools to identify who runs them. The user agent is `custom` if set, e.g. a value of
+/// `AOC_USER_AGENT` like `github.com/me/advent-of-code by me@example.com`. Otherwise, it is the `repository` of the
+/// package in `Cargo.toml`, followed by the name and version of the package.
+fn user_agent(custom: Option<&str>) -> String {
+    let package = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+    match (custom.map(str::trim), env!("CARGO_PKG_REPOSITORY")) {
+        (Some(custom), _) if !custom.is_empty() => custom.into(),
+        (_, "") => package.into(),
+        (_, repository) => format!("{repository} {package}"),
+    }
+}
+
+/// Read the session cookie from the file that aoc-cli uses.
+fn read_session_file() -> Option<String> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map_or_else(|| PathBuf::from(&home).join(".config"), PathBuf::from);
+
+    [
+        PathBuf::from(&home).join(".adventofcode.session"),
+        config_dir.join("adventofcode.session"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Return the inner HTML of every `<article>` element.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Convert the subset of HTML used in puzzle descriptions to markdown.
+/// Code blocks are kept verbatim, so that examples can be copied from them.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start], in_pre);
+
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+
+        let (is_closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name = tag.split_whitespace().next().unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            _ => {}
+        }
+    }
+
+    push_text(&mut out, rest, in_pre);
+
+    // collapse runs of blank lines left behind by nested block elements.
+    let mut markdown = String::new();
+    for line in out.lines() {
+        let line = line.trim_end();
+        if line.is_empty() && (markdown.is_empty() || markdown.ends_with("\n\n")) {
+            continue;
+        }
+        markdown.push_str(line);
+        markdown.push('\n');
+    }
+
+    markdown.trim_end().to_string() + "\n"
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    if in_pre {
+        out.push_str(&decode_entities(text));
+    } else if !text.trim().is_empty() {
+        out.push_str(&decode_entities(&text.replace('\n', " ")));
+    } else if !text.is_empty() && !out.is_empty() && !out.ends_with([' ', '\n']) {
+        // keep whitespace between inline elements, e.g. `<em>a</em> <em>b</em>`.
+        out.push(' ');
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::{extract_articles, html_to_markdown, user_agent, AocClient};
+    use crate::{day, template::backend::AocBackend, year};
+
+    /// Serve a single request with `body`, returning the raw request that was received.
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_inputs() {
+        let (base_url, handle) = mock_server("1abc2\n");
+        let client = AocClient::new(&base_url, "session=abc\n", "me@example.com");
+
+        assert_eq!(client.fetch_input(year!(2023), day!(1)).unwrap(), "1abc2\n");
+
+        let request = handle.join().unwrap();
+        assert_eq!(request.starts_with("GET /2023/day/1/input HTTP/1.1"), true);
+        assert_eq!(request.contains("Cookie: session=abc\r\n"), true);
+        assert_eq!(request.contains("User-Agent: me@example.com\r\n"), true);
+    }
+
+    #[test]
+    fn identifies_operator() {
+        assert_eq!(
+            user_agent(Some("github.com/me/aoc by me@example.com")),
+            "github.com/me/aoc by me@example.com"
+        );
+        assert_eq!(
+            user_agent(Some(" ")).ends_with(concat!("advent_of_code/", env!("CARGO_PKG_VERSION"))),
+            true
+        );
+        assert_eq!(user_agent(None), user_agent(Some("")));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, handle) = mock_server(
+            "<html><main><article><p>That's the right answer!</p></article></main></html>",
+        );
+        let client = AocClient::new(&base_url, "abc", &user_agent(None));
+
+        assert_eq!(
+            client.submit(year!(2023), day!(1), 2, "142").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let request = handle.join().unwrap();
+        assert_eq!(
+            request.starts_with("POST /2023/day/1/answer HTTP/1.1"),
+            true
+        );
+        assert_eq!(request.ends_with("level=2&answer=142"), true);
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><h2>A</h2></article><p>x</p><article class="day-desc"><h2>B</h2></article></main>"#;
+        assert_eq!(extract_articles(html), vec!["<h2>A</h2>", "<h2>B</h2>"]);
+    }
+
+    #[test]
+    fn converts_html_to_markdown() {
+        let html = "<h2>--- Day 1: Trebuchet?! ---</h2><p>For example:</p>\n<pre><code>1abc2\n<em>pqr3stu8vwx</em>\n</code></pre>\n<p>Adding these together produces <code><em>142</em></code> &amp; more.</p>\n<ul>\n<li>one</li>\n<li>two</li>\n</ul>";
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Trebuchet?! ---\n\nFor example:\n\n```\n1abc2\npqr3stu8vwx\n```\n\nAdding these together produces `*142*` & more.\n\n- one\n- two\n"
+        );
+    }
+}
@@ -0,0 +1,299 @@
+/// Module that abstracts over the ways of talking to Advent of Code.
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::{Day, Year};
+
+#[cfg(feature = "http")]
+use crate::template::aoc_client::AocClient;
+
+/// Something that can download puzzles and inputs, and submit answers.
+pub trait AocBackend {
+    /// Human-readable name of the backend, e.g. used in status messages.
+    fn name(&self) -> &'static str;
+
+    /// Fetch the puzzle input of a day.
+    fn fetch_input(&self, year: Year, day: Day) -> Result<String, BackendError>;
+
+    /// Fetch the puzzle description of a day as markdown.
+    fn fetch_puzzle(&self, year: Year, day: Day) -> Result<String, BackendError>;
+
+    /// Submit an answer, returning the text of the response.
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, BackendError>;
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    AocCli(AocCommandError),
+    Http(String),
+    Io(io::Error),
+    MissingSession,
+    MissingOfflineDir,
+    Unavailable,
+}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            BackendError::Http(e) => write!(f, "request failed: {e}"),
+            BackendError::Io(e) => write!(f, "{e}"),
+            BackendError::MissingSession => write!(f, "no session cookie found. Set the AOC_SESSION environment variable or create ~/.adventofcode.session."),
+            BackendError::MissingOfflineDir => write!(f, "no directory found for the offline backend. Set the AOC_OFFLINE_DIR environment variable."),
+            BackendError::Unavailable => write!(f, "no backend available. Configure a session cookie, or run \"cargo install aoc-cli\" to install aoc-cli."),
+        }
+    }
+}
+
+impl From<AocCommandError> for BackendError {
+    fn from(e: AocCommandError) -> Self {
+        BackendError::AocCli(e)
+    }
+}
+
+impl From<io::Error> for BackendError {
+    fn from(e: io::Error) -> Self {
+        BackendError::Io(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Backend that calls the `aoc` executable of aoc-cli.
+pub struct AocCliBackend;
+
+impl AocBackend for AocCliBackend {
+    fn name(&self) -> &'static str {
+        "aoc-cli"
+    }
+
+    fn fetch_input(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        let path = env::temp_dir().join(format!("aoc-input-{year}-{day}.txt"));
+        aoc_cli::download_input(year, day, &path.to_string_lossy())?;
+        let input = fs::read_to_string(&path)?;
+        let _ = fs::remove_file(&path);
+        Ok(input)
+    }
+
+    fn fetch_puzzle(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        let path = env::temp_dir().join(format!("aoc-puzzle-{year}-{day}.md"));
+        aoc_cli::download_puzzle(year, day, &path.to_string_lossy())?;
+        let puzzle = fs::read_to_string(&path)?;
+        let _ = fs::remove_file(&path);
+        Ok(puzzle)
+    }
+
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, BackendError> {
+        let output = match aoc_cli::submit(year, day, part, answer) {
+            Ok(output) | Err(AocCommandError::BadExitStatus(output)) => output,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Backend that reads puzzles and inputs from a directory laid out like `data`, e.g. for working offline.
+///
+/// Inputs are read from `<dir>/inputs/<day>.txt` and puzzles from `<dir>/puzzles/<day>.md`.
+/// Submitted answers are checked against the known correct answers in `<dir>/answers/<day>.json`.
+/// Years other than the primary year are read from `<dir>/<year>`.
+pub struct DirectoryBackend {
+    dir: PathBuf,
+}
+
+impl DirectoryBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn read(&self, year: Year, folder: &str, file: &str) -> Result<String, BackendError> {
+        Ok(fs::read_to_string(
+            year.scope(&self.dir).join(folder).join(file),
+        )?)
+    }
+}
+
+impl AocBackend for DirectoryBackend {
+    fn name(&self) -> &'static str {
+        "offline"
+    }
+
+    fn fetch_input(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        self.read(year, "inputs", &format!("{day}.txt"))
+    }
+
+    fn fetch_puzzle(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        self.read(year, "puzzles", &format!("{day}.md"))
+    }
+
+    /// Respond like Advent of Code would, so that the response can be classified the same way.
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, BackendError> {
+        let answers = Answers::read_in(&year.scope(&self.dir).join("answers"), day);
+
+        let response = match answers.verify(part, Some(answer)) {
+            Verdict::Correct => "That's the right answer!",
+            Verdict::Unknown => "There is no known answer for this part.",
+            Verdict::Wrong => {
+                let expected = answers.get(part).and_then(|x| x.parse::<i128>().ok());
+                match (answer.parse::<i128>().ok(), expected) {
+                    (Some(a), Some(b)) if a > b => {
+                        "That's not the right answer; your answer is too high."
+                    }
+                    (Some(a), Some(b)) if a < b => {
+                        "That's not the right answer; your answer is too low."
+                    }
+                    _ => "That's not the right answer.",
+                }
+            }
+        };
+
+        Ok(response.into())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A call that was made to a [`RecordingBackend`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BackendCall {
+    FetchInput(Year, Day),
+    FetchPuzzle(Year, Day),
+    Submit(Year, Day, u8, String),
+}
+
+/// Fake backend that records every call, and responds with canned data. Useful in tests.
+#[derive(Debug, Default)]
+pub struct RecordingBackend {
+    pub input: String,
+    pub puzzle: String,
+    /// Response text returned for every submission.
+    pub submit_response: String,
+    calls: RefCell<Vec<BackendCall>>,
+}
+
+impl RecordingBackend {
+    pub fn new(input: &str, puzzle: &str, submit_response: &str) -> Self {
+        Self {
+            input: input.into(),
+            puzzle: puzzle.into(),
+            submit_response: submit_response.into(),
+            calls: RefCell::default(),
+        }
+    }
+
+    /// All calls that were made so far, oldest first.
+    pub fn calls(&self) -> Vec<BackendCall> {
+        self.calls.borrow().clone()
+    }
+}
+
+impl AocBackend for RecordingBackend {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    fn fetch_input(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        self.calls
+            .borrow_mut()
+            .push(BackendCall::FetchInput(year, day));
+        Ok(self.input.clone())
+    }
+
+    fn fetch_puzzle(&self, year: Year, day: Day) -> Result<String, BackendError> {
+        self.calls
+            .borrow_mut()
+            .push(BackendCall::FetchPuzzle(year, day));
+        Ok(self.puzzle.clone())
+    }
+
+    fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<String, BackendError> {
+        self.calls
+            .borrow_mut()
+            .push(BackendCall::Submit(year, day, part, answer.into()));
+        Ok(self.submit_response.clone())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Select the backend to use.
+///
+/// The `AOC_BACKEND` environment variable can be set to `http`, `aoc-cli` or `offline` to pick one explicitly.
+/// The offline backend reads from the directory in `AOC_OFFLINE_DIR`.
+/// Otherwise, the built-in HTTP client is used if a session cookie is configured, with aoc-cli as fallback.
+pub fn select() -> Result<Box<dyn AocBackend>, BackendError> {
+    match env::var("AOC_BACKEND").ok().as_deref() {
+        #[cfg(feature = "http")]
+        Some("http") => return Ok(Box::new(AocClient::from_env()?)),
+        Some("offline") => {
+            let dir = env::var_os("AOC_OFFLINE_DIR").ok_or(BackendError::MissingOfflineDir)?;
+            return Ok(Box::new(DirectoryBackend::new(dir)));
+        }
+        Some("aoc-cli") => {
+            aoc_cli::check().map_err(|_| BackendError::Unavailable)?;
+            return Ok(Box::new(AocCliBackend));
+        }
+        _ => {}
+    }
+
+    #[cfg(feature = "http")]
+    match AocClient::from_env() {
+        Ok(client) => return Ok(Box::new(client)),
+        Err(BackendError::MissingSession) => {}
+        Err(e) => return Err(e),
+    }
+
+    match aoc_cli::check() {
+        Ok(()) => Ok(Box::new(AocCliBackend)),
+        Err(_) => Err(BackendError::Unavailable),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{AocBackend, DirectoryBackend};
+    use crate::{day, template::answers::Answers, template::Year};
+
+    #[test]
+    fn reads_from_directory() {
+        let dir = env::temp_dir().join(format!("aoc-offline-{}", std::process::id()));
+        let year = Year::primary().unwrap();
+        let other_year = Year::new(2015).unwrap();
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::create_dir_all(dir.join("2015").join("inputs")).unwrap();
+        fs::write(dir.join("inputs").join("01.txt"), "1abc2\n").unwrap();
+        fs::write(dir.join("2015").join("inputs").join("01.txt"), "(()\n").unwrap();
+        Answers {
+            part_1: Some("142".into()),
+            part_2: Some("abc".into()),
+        }
+        .store_in(&dir.join("answers"), day!(1))
+        .unwrap();
+
+        let backend = DirectoryBackend::new(&dir);
+        let submit = |part, answer| backend.submit(year, day!(1), part, answer).unwrap();
+
+        assert_eq!(backend.fetch_input(year, day!(1)).unwrap(), "1abc2\n");
+        assert_eq!(backend.fetch_input(other_year, day!(1)).unwrap(), "(()\n");
+        assert_eq!(backend.fetch_puzzle(year, day!(1)).is_err(), true);
+        assert_eq!(submit(1, "142").contains("That's the right answer"), true);
+        assert_eq!(submit(1, "150").contains("too high"), true);
+        assert_eq!(submit(1, "-3").contains("too low"), true);
+        assert_eq!(
+            submit(2, "abd").contains("That's not the right answer."),
+            true
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,5 +1,45 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::output::{print_json, OutputFormat};
+use crate::template::run_multi::{print_year_header, run_multi};
+use crate::template::runner::RunOptions;
+use crate::template::{all_days_in, Solution, Year};
+
+pub fn handle(
+    solutions: &[Solution],
+    years: &[Year],
+    is_release: bool,
+    spawn: bool,
+    format: OutputFormat,
+    jobs: usize,
+    timeout: Option<Duration>,
+) {
+    // when `--spawn` is set, ignore the registry so every day falls back to running its binary.
+    let solutions = if spawn { &[] } else { solutions };
+    let options = RunOptions {
+        is_timed: false,
+        format,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let mut results = vec![];
+
+    for (i, year) in years.iter().enumerate() {
+        if years.len() > 1 {
+            print_year_header(*year, i == 0, format);
+        }
+        results.extend(run_multi(
+            solutions,
+            *year,
+            &all_days_in(*year).collect(),
+            is_release,
+            options,
+            jobs,
+        ));
+    }
+
+    if format == OutputFormat::Json {
+        print_json(&results);
+    }
 }
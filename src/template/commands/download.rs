@@ -1,14 +1,91 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::path::{Path, PathBuf};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+use crate::template::backend::{self, AocBackend, BackendError};
+use crate::template::{Day, Year};
+
+pub fn handle(year: Year, day: Day) {
+    let backend = match backend::select() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match download(backend.as_ref(), &year.data_dir(), year, day) {
+        Ok((input_path, puzzle_path)) => {
+            println!("🎄 Successfully wrote input to {input_path:?}.");
+            println!("🎄 Successfully wrote puzzle to {puzzle_path:?}.");
+            if year.is_primary() {
+                println!("🎄 Type `cargo examples {day}` to extract its examples.");
+            } else {
+                println!("🎄 Type `cargo examples {day} --year {year}` to extract its examples.");
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to download via {}: {e}", backend.name());
+            process::exit(1);
+        }
     }
+}
+
+/// Download the input and puzzle of a day into the `inputs` and `puzzles` folders of `data_dir`.
+/// Returns the paths of the written files.
+pub fn download(
+    backend: &dyn AocBackend,
+    data_dir: &Path,
+    year: Year,
+    day: Day,
+) -> Result<(PathBuf, PathBuf), BackendError> {
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let puzzle_path = data_dir.join("puzzles").join(format!("{day}.md"));
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let input = backend.fetch_input(year, day)?;
+    let puzzle = backend.fetch_puzzle(year, day)?;
+
+    fs::create_dir_all(data_dir.join("inputs"))?;
+    fs::create_dir_all(data_dir.join("puzzles"))?;
+
+    fs::write(&input_path, input)?;
+    fs::write(&puzzle_path, puzzle)?;
+
+    Ok((input_path, puzzle_path))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::download;
+    use crate::{
+        day,
+        template::backend::{BackendCall, RecordingBackend},
+        year,
     };
+
+    #[test]
+    fn writes_input_and_puzzle() {
+        let data_dir = env::temp_dir().join(format!("aoc-download-{}", std::process::id()));
+
+        let backend = RecordingBackend::new("1abc2\n", "## --- Day 1 ---\n", "");
+
+        let (input_path, puzzle_path) =
+            download(&backend, &data_dir, year!(2022), day!(1)).unwrap();
+
+        assert_eq!(fs::read_to_string(input_path).unwrap(), "1abc2\n");
+        assert_eq!(
+            fs::read_to_string(puzzle_path).unwrap(),
+            "## --- Day 1 ---\n"
+        );
+        assert_eq!(
+            backend.calls(),
+            vec![
+                BackendCall::FetchInput(year!(2022), day!(1)),
+                BackendCall::FetchPuzzle(year!(2022), day!(1))
+            ]
+        );
+
+        fs::remove_dir_all(data_dir).unwrap();
+    }
 }
@@ -0,0 +1,250 @@
+use std::path::Path;
+use std::{fs, io, process};
+
+use crate::template::puzzle::{parse_examples, PartExample};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{Day, Year};
+
+/// What happened to an example file.
+#[derive(Debug, PartialEq, Eq)]
+enum Written {
+    Created,
+    Unchanged,
+    /// The file holds a different example, which was probably put there by hand.
+    Kept,
+}
+
+pub fn handle(year: Year, day: Day) {
+    let data_dir = year.data_dir();
+    let puzzle_path = data_dir.join("puzzles").join(format!("{day}.md"));
+    let download_cmd = if year.is_primary() {
+        format!("cargo download {day}")
+    } else {
+        format!("cargo download {day} --year {year}")
+    };
+
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle \"{}\": {e}. Run `{download_cmd}` first.",
+                puzzle_path.display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let examples = parse_examples(&markdown);
+    let part_one_input = examples.first().and_then(|x| x.input.clone());
+
+    if part_one_input.is_none() {
+        eprintln!("No example found in \"{}\".", puzzle_path.display());
+        process::exit(1);
+    }
+
+    let module_path = get_path_for_bin(year, day);
+    let original = fs::read_to_string(&module_path).ok();
+    let mut module = original.clone();
+
+    for example in &examples {
+        // part two reuses the example of part one unless it comes with its own.
+        let example_part = match &example.input {
+            Some(input) if example.part > 1 && Some(input) != part_one_input.as_ref() => {
+                Some(example.part)
+            }
+            _ => None,
+        };
+
+        let file = match example_part {
+            Some(part) => format!("{day}-{part}.txt"),
+            None => format!("{day}.txt"),
+        };
+        let path = data_dir.join("examples").join(file);
+
+        let written = match (&example.input, example_part) {
+            (Some(input), _) if example.part == 1 => write_example(&path, input),
+            (Some(input), Some(_)) => write_example(&path, input),
+            _ => Ok(Written::Unchanged),
+        };
+
+        match written {
+            Ok(Written::Created) => println!(
+                "Wrote example of part {} to \"{}\"",
+                example.part,
+                path.display()
+            ),
+            Ok(Written::Unchanged) => {}
+            Ok(Written::Kept) => {
+                println!(
+                    "Kept different example in \"{}\", delete it to extract the example of part {}.",
+                    path.display(),
+                    example.part
+                );
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+
+        if let Some(source) = &mut module {
+            fill_in_answer(source, &module_path, example, example_part);
+        }
+    }
+
+    if let Some(source) = module.filter(|x| Some(x) != original.as_ref()) {
+        if let Err(e) = fs::write(&module_path, source) {
+            eprintln!("Failed to update module file: {e}");
+            process::exit(1);
+        }
+    } else if original.is_none() {
+        println!("Module file \"{module_path}\" does not exist, scaffold the day to add the answers to its tests.");
+    }
+
+    if examples.len() == 1 {
+        println!("---");
+        println!("🎄 Part two is still locked. Run `{download_cmd}` and this command again once it is unlocked.");
+    }
+}
+
+/// Write an example to its file, unless the file already holds a different example.
+fn write_example(path: &Path, input: &str) -> io::Result<Written> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+
+    if existing.trim_end() == input.trim_end() {
+        return Ok(Written::Unchanged);
+    }
+
+    if !existing.trim().is_empty() {
+        return Ok(Written::Kept);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, input)?;
+    Ok(Written::Created)
+}
+
+fn fill_in_answer(
+    source: &mut String,
+    module_path: &str,
+    example: &PartExample,
+    example_part: Option<u8>,
+) {
+    let test = if example.part == 1 {
+        "test_part_one"
+    } else {
+        "test_part_two"
+    };
+
+    match &example.answer {
+        Some(answer) if answer.parse::<i64>().is_ok() => {
+            if let Some(updated) = fill_in_test(source, test, answer, example_part) {
+                *source = updated;
+                println!("Added answer {answer} to `{test}` in \"{module_path}\"");
+            }
+        }
+        Some(answer) => println!(
+            "Found answer `{answer}` for part {}, it is not a number and has to be added to `{test}` by hand.",
+            example.part
+        ),
+        None => println!("No answer found for part {}.", example.part),
+    }
+}
+
+/// Replace `assert_eq!(result, None)` in a test of the template with the answer of its example.
+/// Tests that already have an answer are left alone. Returns `None` if nothing changed.
+fn fill_in_test(
+    source: &str,
+    test: &str,
+    answer: &str,
+    example_part: Option<u8>,
+) -> Option<String> {
+    let start = source.find(&format!("fn {test}()"))?;
+    let end = source[start..]
+        .find("\n    }")
+        .map_or(source.len(), |i| start + i);
+
+    let body = &source[start..end];
+    if !body.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let mut body = body.replacen(
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, Some({answer}));"),
+        1,
+    );
+
+    if let Some(part) = example_part {
+        body = body
+            .replacen(
+                "read_file(\"examples\", DAY)",
+                &format!("read_file_part(\"examples\", DAY, {part})"),
+                1,
+            )
+            .replacen(
+                "read_year_file(\"examples\", YEAR, DAY)",
+                &format!("read_year_file_part(\"examples\", YEAR, DAY, {part})"),
+                1,
+            );
+    }
+
+    Some(format!("{}{body}{}", &source[..start], &source[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{fill_in_test, write_example, Written};
+
+    const MODULE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    #[test]
+    fn fills_in_answers() {
+        let module = fill_in_test(MODULE, "test_part_one", "142", None).unwrap();
+        let module = fill_in_test(&module, "test_part_two", "281", Some(2)).unwrap();
+
+        assert_eq!(module.contains("assert_eq!(result, Some(142));"), true);
+        assert_eq!(module.contains("assert_eq!(result, Some(281));"), true);
+        assert_eq!(
+            module.contains(
+                "part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 2));"
+            ),
+            true
+        );
+        assert_eq!(
+            module.contains("part_one(&advent_of_code::template::read_file(\"examples\", DAY));"),
+            true
+        );
+    }
+
+    #[test]
+    fn keeps_existing_answers() {
+        let module = fill_in_test(MODULE, "test_part_one", "142", None).unwrap();
+
+        assert_eq!(fill_in_test(&module, "test_part_one", "7", None), None);
+        assert_eq!(fill_in_test("", "test_part_one", "7", None), None);
+    }
+
+    #[test]
+    fn keeps_different_examples() {
+        let path = env::temp_dir().join(format!("aoc-examples-{}.txt", std::process::id()));
+
+        assert_eq!(write_example(&path, "1abc2\n").unwrap(), Written::Created);
+        assert_eq!(write_example(&path, "1abc2").unwrap(), Written::Unchanged);
+        assert_eq!(write_example(&path, "a1b2\n").unwrap(), Written::Kept);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1abc2\n");
+
+        fs::write(&path, "").unwrap();
+        assert_eq!(write_example(&path, "a1b2\n").unwrap(), Written::Created);
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+use crate::template::history::{self, format_timestamp};
+use crate::template::output::OutputFormat;
+use crate::template::run_multi::print_year_header;
+use crate::template::{all_days_in, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(years: &[Year], day: Option<Day>) {
+    for (i, year) in years.iter().enumerate() {
+        if years.len() > 1 {
+            print_year_header(*year, i == 0, OutputFormat::Text);
+        }
+        print_history(*year, day);
+    }
+}
+
+fn print_history(year: Year, day: Option<Day>) {
+    let entries = history::read(year);
+
+    if entries.is_empty() {
+        println!("No benchmark runs recorded yet. Run `cargo time --store` to record one.");
+        return;
+    }
+
+    let mut need_space = false;
+
+    for day in all_days_in(year).filter(|d| day.is_none_or(|day| day == *d)) {
+        let rows: Vec<_> = entries
+            .iter()
+            .filter_map(|entry| {
+                let timing = entry.timings.data.iter().find(|t| t.day == day)?;
+                Some((entry, timing))
+            })
+            .collect();
+
+        if rows.is_empty() {
+            continue;
+        }
+
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!(
+            "{:<19}  {:<10}  {:<10}  {:<20}  {:>10}  {:>10}",
+            "Date (UTC)", "Commit", "Baseline", "Machine", "Part 1", "Part 2"
+        );
+
+        for (entry, timing) in rows {
+            let commit = match &entry.commit {
+                Some(commit) if entry.dirty => format!("{commit}*"),
+                Some(commit) => commit.clone(),
+                None => "-".into(),
+            };
+
+            let machine = entry
+                .machine
+                .hostname
+                .clone()
+                .unwrap_or_else(|| format!("{}-{}", entry.machine.os, entry.machine.arch));
+
+            println!(
+                "{:<19}  {:<10}  {:<10}  {:<20}  {:>10}  {:>10}",
+                format_timestamp(entry.timestamp),
+                commit,
+                entry.baseline.as_deref().unwrap_or("-"),
+                machine,
+                timing.part_1.as_deref().unwrap_or("-"),
+                timing.part_2.as_deref().unwrap_or("-"),
+            );
+        }
+    }
+
+    if !need_space {
+        println!("No benchmark runs recorded for this day yet.");
+    }
+}
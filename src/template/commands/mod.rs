@@ -1,6 +1,13 @@
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod history;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod verify;
+#[cfg(feature = "today")]
+pub mod wait;
+pub mod watch;
@@ -1,15 +1,69 @@
-use std::process;
+use std::path::Path;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::backend::{self, AocBackend, BackendError};
+use crate::template::{Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(year: Year, day: Day) {
+    let backend = match backend::select() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match read(backend.as_ref(), &year.data_dir(), year, day) {
+        Ok(puzzle) => println!("{puzzle}"),
+        Err(e) => {
+            eprintln!("failed to read puzzle via {}: {e}", backend.name());
+            process::exit(1);
+        }
     }
+}
+
+/// Fetch the puzzle of a day and write it to the `puzzles` folder of `data_dir`.
+pub fn read(
+    backend: &dyn AocBackend,
+    data_dir: &Path,
+    year: Year,
+    day: Day,
+) -> Result<String, BackendError> {
+    let puzzle = backend.fetch_puzzle(year, day)?;
+    fs::create_dir_all(data_dir.join("puzzles"))?;
+    fs::write(data_dir.join("puzzles").join(format!("{day}.md")), &puzzle)?;
+    Ok(puzzle)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    use super::read;
+    use crate::{
+        day,
+        template::backend::{BackendCall, RecordingBackend},
+        year,
     };
+
+    #[test]
+    fn writes_puzzle() {
+        let data_dir = env::temp_dir().join(format!("aoc-read-{}", std::process::id()));
+
+        let backend = RecordingBackend::new("", "## --- Day 3 ---\n", "");
+
+        let puzzle = read(&backend, &data_dir, year!(2022), day!(3)).unwrap();
+
+        assert_eq!(puzzle, "## --- Day 3 ---\n");
+        assert_eq!(
+            fs::read_to_string(data_dir.join("puzzles").join("03.md")).unwrap(),
+            "## --- Day 3 ---\n"
+        );
+        assert_eq!(
+            backend.calls(),
+            vec![BackendCall::FetchPuzzle(year!(2022), day!(3))]
+        );
+
+        fs::remove_dir_all(data_dir).unwrap();
+    }
 }
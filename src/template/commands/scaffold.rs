@@ -1,28 +1,61 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // keep the contents of existing files, e.g. a downloaded input or a hand-written example.
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Fill in the module template for a day. Solutions of years other than the primary year declare their year,
+/// and read their examples from the data folder of that year.
+fn render_module(year: Year, day: Day) -> String {
+    let module = MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day.into_inner().to_string());
+
+    if year.is_primary() {
+        return module;
+    }
 
-    let mut file = match safe_create_file(&module_path) {
+    module
+        .replacen(
+            &format!("solution!({})", day.into_inner()),
+            &format!("solution!({}, year = {year})", day.into_inner()),
+            1,
+        )
+        .replace(
+            "read_file(\"examples\", DAY)",
+            "read_year_file(\"examples\", YEAR, DAY)",
+        )
+}
+
+pub fn handle(year: Year, day: Day) {
+    let data_dir = year.data_dir();
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let module_path = get_path_for_bin(year, day);
+    let module_path = Path::new(&module_path);
+
+    let mut file = match safe_create_file(module_path) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -30,13 +63,9 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render_module(year, day).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -46,7 +75,7 @@ pub fn handle(day: Day) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -56,7 +85,7 @@ pub fn handle(day: Day) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -65,5 +94,11 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if year.is_primary() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+        println!("🎄 Register `day{day}::SOLUTION` in \"src/solutions.rs\" to run it in-process with `cargo all`.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+        println!("🎄 Register `y{year}_day{day}::SOLUTION` in \"src/solutions.rs\" to run it in-process with `cargo all --year {year}`.");
+    }
 }
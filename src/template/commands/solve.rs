@@ -1,9 +1,33 @@
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{fs, io};
 
-use crate::template::Day;
+use crate::template::output::OutputFormat;
+use crate::template::run_multi::child_commands;
+use crate::template::runner::{PartResult, RunOptions, PARSE_STAGE};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+    input: Option<String>,
+) {
+    if let Some(dir) = input.as_deref().map(Path::new).filter(|x| x.is_dir()) {
+        if format != OutputFormat::Text {
+            eprintln!("`--format {format}` can not be combined with a directory as `--input`.");
+            process::exit(1);
+        }
+        return solve_dir(year, day, release, timeout, dir);
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), year.bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -18,13 +42,32 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if format != OutputFormat::Text {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.as_secs_f64().to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    // stdin is inherited, which allows passing `--input -`.
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    // cargo sets `CARGO_MANIFEST_DIR` for `cargo run`, passing it on changes the fingerprint of
+    // build scripts such as ring's, and rebuilds the http dependencies on every run.
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .env_remove("CARGO_MANIFEST_DIR")
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -32,3 +75,158 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Run a solution against every file in a directory, e.g. the inputs of several accounts, and tabulate the answers.
+fn solve_dir(year: Year, day: Day, release: bool, timeout: Option<Duration>, dir: &Path) {
+    let files = match list_inputs(dir) {
+        Ok(files) if files.is_empty() => {
+            eprintln!("No input files found in \"{}\".", dir.display());
+            process::exit(1);
+        }
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "{ANSI_ITALIC}Running day {day} against {} input(s)...{ANSI_RESET}",
+        files.len()
+    );
+
+    // parts print nothing in JSON mode, the answers are tabulated below.
+    let options = RunOptions {
+        format: OutputFormat::Json,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let rows: Vec<(String, Vec<PartResult>)> = files
+        .iter()
+        .map(|file| {
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            let results =
+                child_commands::run_solution_with_input(year, day, options, release, Some(file))
+                    .unwrap_or_else(|e| {
+                        eprintln!("Failed to run solution for \"{name}\": {e}");
+                        vec![]
+                    });
+            (name.to_string(), results)
+        })
+        .collect();
+
+    println!();
+    print_answers(&rows);
+}
+
+/// Files in a directory, sorted by name. Hidden files are skipped.
+fn list_inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if entry.file_type()?.is_file() && !is_hidden {
+            files.push(entry.path());
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn print_answers(rows: &[(String, Vec<PartResult>)]) {
+    let table: Vec<[String; 3]> = rows
+        .iter()
+        .map(|(name, results)| {
+            [
+                name.clone(),
+                format_cell(results, 1),
+                format_cell(results, 2),
+            ]
+        })
+        .collect();
+
+    let header = ["File".to_string(), "Part 1".into(), "Part 2".into()];
+    let width = |column: usize| {
+        table
+            .iter()
+            .chain([&header])
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or_default()
+    };
+    let (file_width, part_1_width) = (width(0), width(1));
+
+    println!(
+        "{ANSI_BOLD}{:<file_width$}  {:<part_1_width$}  {}{ANSI_RESET}",
+        header[0], header[1], header[2]
+    );
+
+    for [file, part_1, part_2] in &table {
+        println!("{file:<file_width$}  {part_1:<part_1_width$}  {part_2}");
+    }
+}
+
+/// The answer of a part, or why there is none, e.g. `✖ panicked`. A failed parse stage fails every part.
+fn format_cell(results: &[PartResult], part: u8) -> String {
+    let result = results
+        .iter()
+        .find(|x| x.part == part || (x.part == PARSE_STAGE && x.failure.is_some()));
+
+    match result {
+        Some(result) if result.failure.is_some() => {
+            format!("✖ {}", result.status().replace('_', " "))
+        }
+        Some(result) => result.answer.clone().unwrap_or_else(|| "-".into()),
+        None => "-".into(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_cell;
+    use crate::template::runner::{Failure, PartResult, PARSE_STAGE};
+    use crate::{day, year};
+
+    fn solved(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            answer: answer.map(ToString::to_string),
+            failure: None,
+            ..PartResult::failed(
+                year!(2023),
+                day!(1),
+                part,
+                Failure::TimedOut,
+                Duration::ZERO,
+            )
+        }
+    }
+
+    #[test]
+    fn formats_answers_and_failures() {
+        let failed = PartResult::failed(year!(2023), day!(1), 2, Failure::TimedOut, Duration::ZERO);
+        let results = vec![solved(1, Some("142")), failed];
+
+        assert_eq!(format_cell(&results, 1), "142");
+        assert_eq!(format_cell(&results, 2), "✖ timed out");
+        assert_eq!(format_cell(&[solved(1, None)], 1), "-");
+        assert_eq!(format_cell(&[], 1), "-");
+    }
+
+    #[test]
+    fn fails_every_part_if_parsing_failed() {
+        let failed = PartResult::failed(
+            year!(2023),
+            day!(1),
+            PARSE_STAGE,
+            Failure::Panicked("bad input".into()),
+            Duration::ZERO,
+        );
+
+        assert_eq!(format_cell(&[failed], 2), "✖ panicked");
+    }
+}
@@ -0,0 +1,315 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::output::OutputFormat;
+use crate::template::puzzle::parse_examples;
+use crate::template::run_multi::{get_path_for_bin, print_year_header, run_multi};
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    all_days_in, sanitise, Day, InputErrorKind, Solution, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// State of an input or example file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileState {
+    Missing,
+    Empty,
+    /// The file holds an error page or a message instead of an input.
+    Invalid,
+    Present,
+}
+
+impl FileState {
+    fn read(path: &Path) -> Self {
+        match fs::read_to_string(path).map(|x| sanitise(&x)) {
+            Err(_) => FileState::Missing,
+            Ok(Err(InputErrorKind::Placeholder)) => FileState::Invalid,
+            Ok(Err(_)) => FileState::Empty,
+            Ok(Ok(_)) => FileState::Present,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            FileState::Missing => "-",
+            FileState::Empty => "empty",
+            FileState::Invalid => "invalid",
+            FileState::Present => "✔",
+        }
+    }
+}
+
+/// Everything that is known about a day, without running its solution.
+#[derive(Clone, Debug)]
+struct DayStatus {
+    day: Day,
+    is_scaffolded: bool,
+    input: FileState,
+    examples: FileState,
+    /// How many parts of the puzzle description were downloaded.
+    puzzle_parts: usize,
+    /// Whether the tests of the day pass, if they were run.
+    tests_pass: Option<bool>,
+    answers: Answers,
+    /// Whether the solution produces the known answers of both parts, if it was run.
+    verdicts: Option<[Verdict; 2]>,
+    timing: Option<Timing>,
+}
+
+impl DayStatus {
+    fn collect(year: Year, day: Day, timings: &Timings) -> Self {
+        let data_dir = year.data_dir();
+
+        DayStatus {
+            day,
+            is_scaffolded: Path::new(&get_path_for_bin(year, day)).exists(),
+            input: FileState::read(&data_dir.join("inputs").join(format!("{day}.txt"))),
+            examples: examples_state(&data_dir.join("examples"), day),
+            puzzle_parts: fs::read_to_string(data_dir.join("puzzles").join(format!("{day}.md")))
+                .map_or(0, |x| parse_examples(&x).len()),
+            tests_pass: None,
+            answers: Answers::read(year, day),
+            verdicts: None,
+            timing: timings.data.iter().find(|t| t.day == day).cloned(),
+        }
+    }
+
+    fn to_row(&self) -> [String; 10] {
+        let tests = match self.tests_pass {
+            Some(true) => "✔",
+            Some(false) => "✖",
+            None => "-",
+        };
+
+        let answers = [1, 2]
+            .map(|part| {
+                if self.answers.get(part).is_some() {
+                    "✔"
+                } else {
+                    "-"
+                }
+            })
+            .join(" ");
+
+        let verified = match self.verdicts {
+            Some(verdicts) => verdicts.map(Verdict::symbol).join(" "),
+            None => "- -".into(),
+        };
+
+        let puzzle = match self.puzzle_parts {
+            0 => "-",
+            1 => "1",
+            _ => "1+2",
+        };
+
+        let timing = |part: u8| {
+            self.timing
+                .as_ref()
+                .and_then(|t| {
+                    if part == 1 {
+                        t.part_1.clone()
+                    } else {
+                        t.part_2.clone()
+                    }
+                })
+                .unwrap_or_else(|| "-".into())
+        };
+
+        [
+            self.day.to_string(),
+            if self.is_scaffolded { "✔" } else { "-" }.into(),
+            self.input.symbol().into(),
+            self.examples.symbol().into(),
+            puzzle.into(),
+            tests.into(),
+            answers,
+            verified,
+            timing(1),
+            timing(2),
+        ]
+    }
+}
+
+/// Print a table with the state of every day of the given years.
+/// With `run_tests`, the tests of every scaffolded day are run, which compiles them first,
+/// and every scaffolded day with an input is solved to verify its answers.
+pub fn handle(solutions: &[Solution], years: &[Year], run_tests: bool) {
+    for (i, year) in years.iter().enumerate() {
+        if years.len() > 1 {
+            print_year_header(*year, i == 0, OutputFormat::Text);
+        }
+
+        let timings = Timings::read_from_file(*year);
+        let mut rows: Vec<DayStatus> = all_days_in(*year)
+            .map(|day| DayStatus::collect(*year, day, &timings))
+            .collect();
+
+        if run_tests && rows.iter().any(|x| x.is_scaffolded) {
+            println!("Running tests...");
+            run_cargo_test(&["--bins", "--no-run"]);
+
+            for row in rows.iter_mut().filter(|x| x.is_scaffolded) {
+                row.tests_pass = Some(run_cargo_test(&["--bin", &year.bin_name(row.day)]));
+            }
+
+            println!("Verifying answers...");
+            let days = rows
+                .iter()
+                .filter(|x| x.is_scaffolded && x.input == FileState::Present)
+                .map(|x| x.day)
+                .collect();
+            // parts print nothing in JSON mode, the verdicts are tabulated below.
+            let options = RunOptions {
+                format: OutputFormat::Json,
+                ..RunOptions::default()
+            };
+            let results = run_multi(solutions, *year, &days, true, options, 1);
+
+            for row in rows.iter_mut().filter(|x| days.contains(&x.day)) {
+                row.verdicts = Some(get_verdicts(&results, row));
+            }
+            println!();
+        }
+
+        for line in format_table(&rows) {
+            println!("{line}");
+        }
+    }
+}
+
+/// The verdict of each part of a day. A part without a result, e.g. because parsing failed, has no answer.
+fn get_verdicts(results: &[PartResult], status: &DayStatus) -> [Verdict; 2] {
+    [1, 2].map(|part| {
+        results
+            .iter()
+            .find(|x| x.day == status.day && x.part == part)
+            .map_or_else(|| status.answers.verify(part, None), |x| x.verdict)
+    })
+}
+
+/// Whether `cargo test` succeeds with the given arguments. Its output is discarded.
+fn run_cargo_test(args: &[&str]) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet"])
+        .args(args)
+        .env_remove("CARGO_MANIFEST_DIR")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|x| x.success())
+}
+
+/// The examples of a day are present if it has an example manifest or example files, and none of them are empty.
+fn examples_state(dir: &Path, day: Day) -> FileState {
+    let mut states: Vec<FileState> = [format!("{day}.txt")]
+        .into_iter()
+        .chain((1..=2).map(|part| format!("{day}-{part}.txt")))
+        .map(|file| FileState::read(&dir.join(file)))
+        .filter(|x| *x != FileState::Missing)
+        .collect();
+
+    if dir.join(format!("{day}.json")).exists() {
+        states.push(FileState::Present);
+    }
+
+    states
+        .iter()
+        .copied()
+        .find(|x| *x != FileState::Present)
+        .or(states.first().copied())
+        .unwrap_or(FileState::Missing)
+}
+
+fn format_table(rows: &[DayStatus]) -> Vec<String> {
+    let header = [
+        "Day", "Bin", "Input", "Examples", "Puzzle", "Tests", "Answers", "Verified", "Part 1",
+        "Part 2",
+    ]
+    .map(String::from);
+
+    let table: Vec<[String; 10]> = rows.iter().map(DayStatus::to_row).collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            table
+                .iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |row: &[String; 10]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header))];
+    lines.extend(table.iter().map(format_row));
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{examples_state, format_table, DayStatus, FileState};
+    use crate::day;
+    use crate::template::answers::{Answers, Verdict};
+
+    #[test]
+    fn reads_example_states() {
+        let dir = env::temp_dir().join(format!("aoc-status-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(examples_state(&dir, day!(1)), FileState::Missing);
+
+        fs::write(dir.join("01.txt"), "1abc2").unwrap();
+        assert_eq!(examples_state(&dir, day!(1)), FileState::Present);
+
+        fs::write(dir.join("01-2.txt"), "").unwrap();
+        assert_eq!(examples_state(&dir, day!(1)), FileState::Empty);
+
+        fs::write(dir.join("02.json"), "[]").unwrap();
+        assert_eq!(examples_state(&dir, day!(2)), FileState::Present);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn formats_table() {
+        let status = DayStatus {
+            day: day!(1),
+            is_scaffolded: true,
+            input: FileState::Present,
+            examples: FileState::Empty,
+            puzzle_parts: 2,
+            tests_pass: Some(false),
+            answers: Answers {
+                part_1: Some("142".into()),
+                part_2: None,
+            },
+            verdicts: Some([Verdict::Wrong, Verdict::Unknown]),
+            timing: None,
+        };
+
+        let lines = format_table(&[status]);
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("Day  Bin  Input  Examples  Puzzle"));
+        assert_eq!(
+            lines[1],
+            "01   ✔    ✔      empty     1+2     ✖      ✔ -      ✖ ?       -       -"
+        );
+    }
+}
@@ -1,19 +1,121 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::compare;
+use crate::template::history::{self, HistoryEntry};
+use crate::template::output::{print_json, OutputFormat};
+use crate::template::readme_benchmarks::{self, TableStyle};
+use crate::template::reports::{self, ReportFormat};
+use crate::template::run_multi::{child_commands, print_year_header, run_multi};
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::timings::{is_valid_baseline_name, Timings};
+use crate::template::{all_days_in, Day, Solution, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Command-line options of the `time` command.
+#[derive(Clone, Debug, Default)]
+pub struct TimeOptions {
+    pub day: Option<Day>,
+    pub run_all: bool,
+    pub store: bool,
+    pub spawn: bool,
+    pub format: OutputFormat,
+    /// Show the median and standard deviation instead of the mean in the readme table.
+    pub readme_median: bool,
+    /// Compare the results against the stored timings.
+    pub compare: bool,
+    /// Relative slowdown in percent above which a part counts as a regression when comparing.
+    pub threshold_pct: f64,
+    /// Compare the results against this named baseline instead of the stored timings. Implies `compare`.
+    pub baseline: Option<String>,
+    /// Save the results as this named baseline.
+    pub save_baseline: Option<String>,
+    /// Number of days to bench concurrently. Benching runs serially unless this is set, to keep measurements clean.
+    pub jobs: usize,
+    /// Give up on a part if it does not finish within this duration, including benching.
+    pub timeout: Option<Duration>,
+    /// Record the heap usage of each part in a separate run with the `dhat-heap` feature.
+    pub memory: bool,
+    /// Write a standalone report of the stored and new timings in each of these formats.
+    pub reports: Vec<ReportFormat>,
+}
+
+/// Bench every year in `years` in turn. Timings, baselines and the history are kept per year.
+pub fn handle(solutions: &[Solution], years: &[Year], opts: TimeOptions) {
+    if let Some(name) = [&opts.baseline, &opts.save_baseline]
+        .into_iter()
+        .flatten()
+        .find(|name| !is_valid_baseline_name(name))
+    {
+        eprintln!("Invalid baseline name `{name}`. Use letters, digits, `-`, `_` and `.` only.");
+        process::exit(1);
+    }
+
+    let mut results = vec![];
+    let mut has_regressions = false;
+
+    for (i, year) in years.iter().enumerate() {
+        if years.len() > 1 {
+            print_year_header(*year, i == 0, opts.format);
+        }
+        let (year_results, year_has_regressions) = handle_year(solutions, *year, opts.clone());
+        results.extend(year_results);
+        has_regressions |= year_has_regressions;
+    }
+
+    if opts.format == OutputFormat::Json {
+        print_json(&results);
+    }
+
+    if has_regressions {
+        eprintln!(
+            "Found regressions above the threshold of {}%.",
+            opts.threshold_pct
+        );
+        process::exit(1);
+    }
+}
+
+/// Bench the days of a single year. Returns the results and whether any part regressed.
+fn handle_year(solutions: &[Solution], year: Year, opts: TimeOptions) -> (Vec<PartResult>, bool) {
+    let TimeOptions {
+        day,
+        run_all,
+        store,
+        spawn,
+        format,
+        readme_median,
+        compare,
+        threshold_pct,
+        baseline,
+        save_baseline,
+        jobs,
+        timeout,
+        memory,
+        reports: report_formats,
+    } = opts;
+
+    let stored_timings = Timings::read_from_file(year);
+
+    let compare = compare || baseline.is_some();
+    let reference_timings = match &baseline {
+        Some(name) => Timings::read_baseline(year, name).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        }),
+        None => stored_timings.clone(),
+    };
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days_in(year).collect()
+            } else if compare {
+                // when comparing, re-run every day that has stored timings.
+                reference_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days_in(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +123,150 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // when `--spawn` is set, ignore the registry so every day falls back to running its binary.
+    let solutions = if spawn { &[] } else { solutions };
+    let options = RunOptions {
+        is_timed: true,
+        format,
+        timeout,
+        ..RunOptions::default()
+    };
+    let mut results = run_multi(solutions, year, &days_to_run, true, options, jobs);
+
+    if memory {
+        record_memory(year, &days_to_run, &mut results, format, timeout);
+    }
+
+    let timings = Timings::from_results(&results);
+
+    let mut has_regressions = false;
+
+    if compare {
+        let comparisons = compare::compare(&reference_timings, &timings);
+        has_regressions = comparisons.iter().any(|c| c.is_regression(threshold_pct));
+
+        print_status(format, &compare::format_table(&comparisons, threshold_pct));
+    }
+
+    if let Some(name) = &save_baseline {
+        let merged_timings = Timings::read_baseline(year, name)
+            .unwrap_or_default()
+            .merge(&timings);
+
+        match merged_timings.store_baseline(year, name) {
+            Ok(()) => print_status(format, &format!("Saved baseline `{name}`.")),
+            Err(e) => eprintln!("Failed to save baseline `{name}`: {e}"),
+        }
+    }
+
+    if store || save_baseline.is_some() {
+        let entry = HistoryEntry::new(timings.clone(), save_baseline.clone());
+        if let Err(e) = history::append(year, &entry) {
+            eprintln!("Failed to append run to history: {e}");
+        }
+    }
+
+    let style = if readme_median {
+        TableStyle::Median
+    } else {
+        TableStyle::Mean
+    };
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, style) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                print_status(format, "Stored updated benchmarks.");
+            }
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
+            }
+        }
+    }
+
+    if !report_formats.is_empty() {
+        // reports cover every benched day, not only the ones of this run.
+        let merged_timings = stored_timings.merge(&timings);
+
+        for report in report_formats {
+            match reports::write(report, year, &merged_timings, style) {
+                Ok(path) => print_status(
+                    format,
+                    &format!("Wrote {report} report to \"{}\".", path.display()),
+                ),
+                Err(e) => eprintln!("Failed to write {report} report: {e}"),
+            }
+        }
+    }
+
+    (results, has_regressions)
+}
+
+/// Run every day once more with the `dhat-heap` feature, and add the heap usage of each part to its result.
+/// This is a separate run, as tracking allocations slows solutions down too much to bench them at the same time.
+fn record_memory(
+    year: Year,
+    days: &HashSet<Day>,
+    results: &mut [PartResult],
+    format: OutputFormat,
+    timeout: Option<Duration>,
+) {
+    let is_text = format == OutputFormat::Text;
+    if is_text {
+        println!();
+        println!("{ANSI_BOLD}Memory{ANSI_RESET}");
+        println!("------");
+    }
+
+    // parts print nothing in JSON mode, their memory is printed below.
+    let options = RunOptions {
+        format: OutputFormat::Json,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    for day in all_days_in(year).filter(|day| days.contains(day)) {
+        let memory_results = match child_commands::profile_memory(year, day, options) {
+            Ok(memory_results) => memory_results,
+            Err(e) => {
+                eprintln!("Failed to record memory of day {day}: {e}");
+                continue;
+            }
+        };
+
+        for memory_result in memory_results {
+            let Some(memory) = memory_result.memory else {
+                continue;
+            };
+
+            if is_text {
+                let label = if memory_result.is_parse_stage() {
+                    "Parse".into()
+                } else {
+                    format!("Part {}", memory_result.part)
+                };
+                println!("Day {day} {label}: {}", memory.summary());
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+
+            if let Some(result) = results
+                .iter_mut()
+                .find(|r| r.day == day && r.part == memory_result.part)
+            {
+                result.memory = Some(memory);
             }
         }
     }
 }
+
+/// Print a status message after the results.
+/// When printing machine-readable output, the message goes to stderr to keep stdout parseable.
+fn print_status(format: OutputFormat, message: &str) {
+    if format == OutputFormat::Text {
+        println!();
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+}
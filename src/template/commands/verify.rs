@@ -0,0 +1,130 @@
+use std::process;
+use std::time::Duration;
+
+use crate::template::answers::{get_answers_dir, Answers, Verdict};
+use crate::template::output::{print_json, OutputFormat};
+use crate::template::run_multi::{print_year_header, run_multi};
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::{all_days_in, Solution, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Run every day of the given years and check its answers against the known correct answers. Exits with an error on any mismatch.
+/// With `record`, answers of parts that have no known correct answer yet are stored as correct.
+pub fn handle(
+    solutions: &[Solution],
+    years: &[Year],
+    spawn: bool,
+    record: bool,
+    format: OutputFormat,
+    jobs: usize,
+    timeout: Option<Duration>,
+) {
+    // when `--spawn` is set, ignore the registry so every day falls back to running its binary.
+    let solutions = if spawn { &[] } else { solutions };
+    let options = RunOptions {
+        is_timed: false,
+        format,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let mut results = vec![];
+
+    for (i, year) in years.iter().enumerate() {
+        if years.len() > 1 {
+            print_year_header(*year, i == 0, format);
+        }
+        results.extend(run_multi(
+            solutions,
+            *year,
+            &all_days_in(*year).collect(),
+            true,
+            options,
+            jobs,
+        ));
+    }
+
+    if format == OutputFormat::Json {
+        print_json(&results);
+    }
+
+    if record {
+        record_answers(&results, years, format);
+    }
+
+    // the parse stage of a solution has no answer to verify.
+    let count = |verdict: Verdict| {
+        results
+            .iter()
+            .filter(|r| !r.is_parse_stage() && r.verdict == verdict)
+            .count()
+    };
+    let (correct, wrong, unknown) = (
+        count(Verdict::Correct),
+        count(Verdict::Wrong),
+        count(Verdict::Unknown),
+    );
+
+    let summary = format!(
+        "{ANSI_BOLD}Verified{ANSI_RESET}: {correct} correct, {wrong} wrong, {unknown} unknown."
+    );
+
+    if format == OutputFormat::Text {
+        println!();
+        println!("{summary}");
+    } else {
+        eprintln!("{summary}");
+    }
+
+    if wrong > 0 {
+        for result in results.iter().filter(|r| r.verdict == Verdict::Wrong) {
+            eprintln!(
+                "Day {} part {}: expected {}, got {}.",
+                result.year.bin_name(result.day),
+                result.part,
+                Answers::read(result.year, result.day)
+                    .get(result.part)
+                    .unwrap_or("?"),
+                result.answer.as_deref().unwrap_or("no answer")
+            );
+        }
+        process::exit(1);
+    }
+}
+
+fn record_answers(results: &[PartResult], years: &[Year], format: OutputFormat) {
+    let mut recorded = 0;
+
+    for result in results {
+        let Some(answer) = &result.answer else {
+            continue;
+        };
+
+        if result.verdict != Verdict::Unknown {
+            continue;
+        }
+
+        let mut answers = Answers::read(result.year, result.day);
+        answers.set(result.part, answer);
+
+        match answers.store(result.year, result.day) {
+            Ok(()) => recorded += 1,
+            Err(e) => eprintln!(
+                "Failed to record answer for day {}: {e}",
+                result.year.bin_name(result.day)
+            ),
+        }
+    }
+
+    let dirs = years
+        .iter()
+        .map(|year| get_answers_dir(*year).display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = format!("Recorded {recorded} new answer(s) to {dirs}.");
+    if format == OutputFormat::Text {
+        println!();
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+}
@@ -0,0 +1,48 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use chrono::Local;
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::unlock::{format_countdown, next_unlock, wait_until, Clock, SystemClock};
+use crate::template::Year;
+
+/// Time to wait after an unlock before downloading, in case the local clock is slightly ahead of the server.
+const UNLOCK_GRACE: Duration = Duration::from_secs(2);
+
+/// Wait for the next puzzle of `year` to unlock with a countdown, then scaffold, download and read it.
+pub fn handle(year: Year) {
+    let clock = SystemClock;
+
+    let Some((day, unlock)) = next_unlock(&clock, year) else {
+        eprintln!("Every puzzle of {year} is unlocked already. Use `cargo scaffold <day> --download` instead.");
+        process::exit(1);
+    };
+
+    println!(
+        "Waiting for day {day} of {year}, which unlocks at {}.",
+        unlock.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+    );
+
+    wait_until(&clock, unlock, |remaining| {
+        print!(
+            "\r⏳ Day {day} unlocks in {}\x1b[K",
+            format_countdown(remaining)
+        );
+        // the countdown is cosmetic, a failed flush only delays it.
+        let _ = io::stdout().flush();
+    });
+
+    println!("\r🔓 Day {day} is unlocked.\x1b[K");
+    clock.sleep(UNLOCK_GRACE);
+
+    // the solution may have been scaffolded ahead of time.
+    if !Path::new(&get_path_for_bin(year, day)).exists() {
+        scaffold::handle(year, day);
+    }
+    download::handle(year, day);
+    read::handle(year, day);
+}
@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::output::OutputFormat;
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::runner::RunOptions;
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The outcome of a single test of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+impl TestOutcome {
+    fn symbol(self) -> &'static str {
+        match self {
+            TestOutcome::Passed => "✔",
+            TestOutcome::Failed => "✖",
+            TestOutcome::Ignored => "-",
+        }
+    }
+}
+
+/// What a single run of the tests and the solution produced, to compare against the next run.
+#[derive(Clone, Debug, Default)]
+struct Run {
+    tests: Vec<(String, TestOutcome)>,
+    answers: Vec<(u8, Option<String>)>,
+}
+
+/// Re-run the example tests and the solution of a day whenever its source, examples or input change.
+pub fn handle(year: Year, day: Day, release: bool, timeout: Option<Duration>) {
+    let mut snapshot = take_snapshot(&watched_paths(year, day));
+    let mut previous: Option<Run> = None;
+
+    loop {
+        previous = Some(run(year, day, release, timeout, previous.as_ref()));
+
+        println!();
+        println!(
+            "{ANSI_ITALIC}👀 Watching {} file(s) for changes. Press Ctrl-C to stop.{ANSI_RESET}",
+            snapshot.len()
+        );
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = take_snapshot(&watched_paths(year, day));
+            if next != snapshot {
+                snapshot = next;
+                break;
+            }
+        }
+
+        println!();
+        println!("{ANSI_BOLD}🎄 Change detected, re-running day {day}...{ANSI_RESET}");
+    }
+}
+
+/// The source of a day, its examples (`DD.txt`, `DD-N.txt` and the manifest `DD.json`) and its input.
+fn watched_paths(year: Year, day: Day) -> Vec<PathBuf> {
+    let data_dir = year.data_dir();
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(year, day)),
+        data_dir.join("inputs").join(format!("{day}.txt")),
+    ];
+
+    let examples_dir = data_dir.join("examples");
+    let prefix = day.to_string();
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(&prefix) && (name.ends_with(".txt") || name.ends_with(".json")) {
+                paths.push(entry.path());
+            }
+        }
+    }
+
+    paths
+}
+
+/// Modification times of a set of paths. Missing files are part of the snapshot, so that creating them counts as a change.
+fn take_snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|x| x.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+fn run(
+    year: Year,
+    day: Day,
+    release: bool,
+    timeout: Option<Duration>,
+    previous: Option<&Run>,
+) -> Run {
+    let bin_name = year.bin_name(day);
+    let mut args = vec!["test", "--bin", &bin_name];
+    if release {
+        args.push("--release");
+    }
+
+    println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+    println!("------");
+
+    let tests = match Command::new("cargo")
+        .args(&args)
+        .env_remove("CARGO_MANIFEST_DIR")
+        .output()
+    {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let tests = parse_test_output(&stdout);
+
+            if tests.is_empty() && !output.status.success() {
+                println!("✖ {ANSI_ITALIC}build failed{ANSI_RESET}");
+                eprintln!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+            }
+
+            for (name, outcome) in &tests {
+                let was = previous
+                    .and_then(|run| run.tests.iter().find(|(n, _)| n == name))
+                    .map(|(_, outcome)| *outcome);
+                println!(
+                    "{} {name}{}",
+                    outcome.symbol(),
+                    describe_change(*outcome, was)
+                );
+
+                if *outcome == TestOutcome::Failed {
+                    for line in extract_failure(&stdout, name) {
+                        println!("    {line}");
+                    }
+                }
+            }
+
+            tests
+        }
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            vec![]
+        }
+    };
+
+    println!();
+    println!("{ANSI_BOLD}Solution{ANSI_RESET}");
+    println!("------");
+
+    let options = RunOptions {
+        is_timed: false,
+        format: OutputFormat::Text,
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let answers = match child_commands::run_solution(year, day, options, release) {
+        Ok(results) => results
+            .into_iter()
+            .map(|result| (result.part, result.answer))
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to run solution: {e}");
+            vec![]
+        }
+    };
+
+    if let Some(previous) = previous {
+        for (part, answer) in &answers {
+            let Some((_, was)) = previous.answers.iter().find(|(p, _)| p == part) else {
+                continue;
+            };
+            if was != answer {
+                println!(
+                    "{ANSI_ITALIC}Part {part} changed: {} → {}{ANSI_RESET}",
+                    was.as_deref().unwrap_or("✖"),
+                    answer.as_deref().unwrap_or("✖")
+                );
+            }
+        }
+    }
+
+    Run { tests, answers }
+}
+
+fn describe_change(outcome: TestOutcome, was: Option<TestOutcome>) -> String {
+    match (outcome, was) {
+        (_, None) => String::new(),
+        (now, Some(was)) if now == was => String::new(),
+        (TestOutcome::Passed, Some(TestOutcome::Failed)) => {
+            format!(" {ANSI_ITALIC}(fixed){ANSI_RESET}")
+        }
+        (TestOutcome::Failed, Some(TestOutcome::Passed)) => {
+            format!(" {ANSI_ITALIC}(broke){ANSI_RESET}")
+        }
+        (_, Some(was)) => format!(" {ANSI_ITALIC}(was {}){ANSI_RESET}", was.symbol()),
+    }
+}
+
+/// Parse the test results from the output of `cargo test`, e.g. `test tests::test_part_one ... ok`.
+pub fn parse_test_output(stdout: &str) -> Vec<(String, TestOutcome)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, outcome) = line.strip_prefix("test ")?.split_once(" ... ")?;
+            let outcome = match outcome.trim() {
+                "ok" => TestOutcome::Passed,
+                "FAILED" => TestOutcome::Failed,
+                x if x.starts_with("ignored") => TestOutcome::Ignored,
+                _ => return None,
+            };
+            let name = name.trim().trim_start_matches("tests::");
+            Some((name.to_string(), outcome))
+        })
+        .collect()
+}
+
+/// The output of a failed test, e.g. the assertion that failed.
+fn extract_failure(stdout: &str, name: &str) -> Vec<String> {
+    let headers = [
+        format!("---- tests::{name} stdout ----"),
+        format!("---- {name} stdout ----"),
+    ];
+
+    stdout
+        .lines()
+        .skip_while(|line| !headers.iter().any(|header| line == header))
+        .skip(1)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty() && !line.starts_with("---- "))
+        .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_failure, parse_test_output, TestOutcome};
+
+    const OUTPUT: &str = "
+running 3 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+test tests::test_slow ... ignored, too slow
+
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' panicked at src/bin/01.rs:40:9:
+assertion `left == right` failed
+  left: Some(280)
+ right: Some(281)
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    #[test]
+    fn parses_test_output() {
+        assert_eq!(
+            parse_test_output(OUTPUT),
+            vec![
+                ("test_part_one".to_string(), TestOutcome::Passed),
+                ("test_part_two".to_string(), TestOutcome::Failed),
+                ("test_slow".to_string(), TestOutcome::Ignored),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_failures() {
+        assert_eq!(extract_failure(OUTPUT, "test_part_one").is_empty(), true);
+        assert_eq!(
+            extract_failure(OUTPUT, "test_part_two"),
+            vec![
+                "thread 'tests::test_part_two' panicked at src/bin/01.rs:40:9:",
+                "assertion `left == right` failed",
+                "  left: Some(280)",
+                " right: Some(281)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+/// Module that compares benchmark timings against previously stored timings.
+use std::time::Duration;
+
+use crate::template::runner::PARSE_STAGE;
+use crate::template::stats::BenchStats;
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// The change in execution time of a single part between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+    /// Whether the median was compared. If either side was stored without statistics, the mean is compared.
+    pub is_median: bool,
+}
+
+impl Comparison {
+    /// Relative change of the execution time in percent. Positive values mean the part got slower.
+    pub fn change_pct(&self) -> f64 {
+        if self.before_nanos == 0.0 {
+            return 0.0;
+        }
+        (self.after_nanos - self.before_nanos) / self.before_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_pct: f64) -> bool {
+        self.change_pct() > threshold_pct
+    }
+}
+
+/// Compare every part that is benched in both `before` and `after`.
+pub fn compare(before: &Timings, after: &Timings) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+
+    for new in &after.data {
+        let Some(old) = before.data.iter().find(|t| t.day == new.day) else {
+            continue;
+        };
+
+        for part in [PARSE_STAGE, 1, 2] {
+            let median_nanos = |stats: Option<&BenchStats>| {
+                #[allow(clippy::cast_precision_loss)]
+                stats.map(|s| s.median.as_nanos() as f64)
+            };
+
+            let values = match (median_nanos(old.stats(part)), median_nanos(new.stats(part))) {
+                (Some(before_nanos), Some(after_nanos)) => Some((before_nanos, after_nanos, true)),
+                _ => old
+                    .mean_nanos(part)
+                    .zip(new.mean_nanos(part))
+                    .map(|(before_nanos, after_nanos)| (before_nanos, after_nanos, false)),
+            };
+
+            if let Some((before_nanos, after_nanos, is_median)) = values {
+                comparisons.push(Comparison {
+                    day: new.day,
+                    part,
+                    before_nanos,
+                    after_nanos,
+                    is_median,
+                });
+            }
+        }
+    }
+
+    comparisons
+}
+
+/// Render comparisons as a table, flagging every part that regressed by more than `threshold_pct`.
+pub fn format_table(comparisons: &[Comparison], threshold_pct: f64) -> String {
+    let mut lines: Vec<String> = vec![
+        format!("{ANSI_BOLD}Comparison{ANSI_RESET} (regression threshold: {threshold_pct}%)"),
+        String::new(),
+        format!(
+            "{:<6} {:<6} {:>12} {:>12} {:>9}",
+            "Day", "Part", "Before", "After", "Change"
+        ),
+    ];
+
+    for comparison in comparisons {
+        let status = if comparison.is_regression(threshold_pct) {
+            "▲ regression"
+        } else if comparison.change_pct() < -threshold_pct {
+            "▼ improvement"
+        } else {
+            ""
+        };
+
+        let metric = if comparison.is_median { "" } else { " (mean)" };
+        let part = if comparison.part == PARSE_STAGE {
+            "parse".into()
+        } else {
+            comparison.part.to_string()
+        };
+
+        lines.push(
+            format!(
+                "{:<6} {:<6} {:>12} {:>12} {:>+8.1}% {status}{metric}",
+                comparison.day.to_string(),
+                part,
+                format_nanos(comparison.before_nanos),
+                format_nanos(comparison.after_nanos),
+                comparison.change_pct(),
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+
+    if comparisons.is_empty() {
+        lines.push("No stored timings to compare against.".into());
+    }
+
+    lines.join("\n")
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, format_table};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(day: crate::template::Day, part_1: &str, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+            total_nanos: 0_f64,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            parse_memory: None,
+        }
+    }
+
+    #[test]
+    fn compares_mean_timings() {
+        let before = Timings {
+            data: vec![timing(day!(1), "10.0ms", Some("20.0ms"))],
+        };
+        let after = Timings {
+            data: vec![timing(day!(1), "12.0ms", Some("19.0ms"))],
+        };
+
+        let comparisons = compare(&before, &after);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].is_median, false);
+        assert_eq!(comparisons[0].change_pct().round(), 20.0);
+        assert_eq!(comparisons[0].is_regression(10.0), true);
+        assert_eq!(comparisons[1].change_pct().round(), -5.0);
+        assert_eq!(comparisons[1].is_regression(10.0), false);
+    }
+
+    #[test]
+    fn prefers_median_timings() {
+        let mut before = timing(day!(1), "10.0ms", None);
+        before.part_1_stats = Some(BenchStats {
+            median: Duration::from_millis(8),
+            ..BenchStats::default()
+        });
+        let mut after = timing(day!(1), "20.0ms", None);
+        after.part_1_stats = Some(BenchStats {
+            median: Duration::from_millis(8),
+            ..BenchStats::default()
+        });
+
+        let comparisons = compare(
+            &Timings { data: vec![before] },
+            &Timings { data: vec![after] },
+        );
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].is_median, true);
+        assert_eq!(comparisons[0].change_pct(), 0.0);
+    }
+
+    #[test]
+    fn skips_days_without_stored_timings() {
+        let before = Timings {
+            data: vec![timing(day!(1), "10.0ms", None)],
+        };
+        let after = Timings {
+            data: vec![timing(day!(2), "10.0ms", None)],
+        };
+        assert_eq!(compare(&before, &after).is_empty(), true);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let before = Timings {
+            data: vec![timing(day!(1), "10.0ms", Some("20.0ms"))],
+        };
+        let after = Timings {
+            data: vec![timing(day!(1), "12.0ms", Some("10.0ms"))],
+        };
+        let table = format_table(&compare(&before, &after), 10.0);
+        assert_eq!(table.matches("▲ regression").count(), 1);
+        assert_eq!(table.matches("▼ improvement").count(), 1);
+    }
+}
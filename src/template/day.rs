@@ -3,12 +3,14 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use crate::template::unlock;
+use crate::template::Year;
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+/// The largest number of puzzles an event can have, so that days still display as two digits.
+pub const MAX_DAYS: u8 = 99;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of an event, i.e. an integer in range 1 to the number of puzzles of the event.
+/// Events up to 2024 have 25 puzzles, later ones 12. Set `AOC_DAYS` to change it for the primary year, see [`Year::days`].
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,10 +24,26 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] from the provided value if it's a day of the primary year's event,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::new_in_range(day, Year::__primary_days_const())
+    }
+
+    /// Creates a [`Day`] from the provided value if it's a day of the event of `year`, returns [`None`] otherwise.
+    pub fn new_in(year: Year, day: u8) -> Option<Self> {
+        Self::new_in_range(day, year.days())
+    }
+
+    /// Parses a [`Day`] of the event of `year`, e.g. from the command-line.
+    pub fn parse_in(year: Year, s: &str) -> Result<Self, DayFromStrError> {
+        let error = DayFromStrError { days: year.days() };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new_in(year, day).ok_or(error)
+    }
+
+    fn new_in_range(day: u8, days: u8) -> Option<Self> {
+        if day == 0 || day > days {
             return None;
         }
         Some(Self(day))
@@ -45,15 +63,10 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+    /// Returns the current day if it's a december day of the event of `year`, `None` otherwise.
+    /// See [`unlock::current_day`] to pass a different clock.
+    pub fn today(year: Year) -> Option<Self> {
+        unlock::current_day(&unlock::SystemClock, year)
     }
 }
 
@@ -80,40 +93,63 @@ impl PartialOrd<u8> for Day {
 impl FromStr for Day {
     type Err = DayFromStrError;
 
+    /// Parses a [`Day`] of the primary year's event. Use [`Day::parse_in`] for other years.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let error = DayFromStrError {
+            days: Year::__primary_days_const(),
+        };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new(day).ok_or(error)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy)]
+pub struct DayFromStrError {
+    /// The number of puzzles of the event the day was parsed for.
+    pub days: u8,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.days)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the primary year's event, from the 1st to its last puzzle.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event of `year`, from the 1st to its last puzzle.
+pub fn all_days_in(year: Year) -> AllDays {
+    AllDays::new_in(year)
+}
+
+/// An iterator that yields every day of an event, from the 1st to its last puzzle.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self {
+            current: 1,
+            last: Year::__primary_days_const(),
+        }
+    }
+
+    pub fn new_in(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.days(),
+        }
     }
 }
 
@@ -121,10 +157,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,15 +171,27 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// The day is checked against the event of the primary year, or of the year passed with `year = ...`.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::Year::__primary_days_const(),
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a day of the event set in AOC_YEAR and AOC_DAYS"
+            ),
+        );
+        $crate::template::Day::__new_unchecked($day)
+    }};
+    ($day:expr, year = $year:expr) => {{
+        const _ASSERT: () = assert!(
+            $day != 0 && $day <= $crate::template::Year::__days_const($year),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, expecting a day of the event of that year"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,7 +202,8 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, all_days_in, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +236,22 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn days_depend_on_event() {
+        assert_eq!(all_days_in(year!(2024)).count(), 25);
+        assert_eq!(all_days_in(year!(2025)).count(), 12);
+
+        assert_eq!(Day::new_in(year!(2024), 25), Some(Day(25)));
+        assert_eq!(Day::new_in(year!(2025), 13), None);
+        assert_eq!(Day::new_in(year!(2025), 0), None);
+
+        assert_eq!(Day::parse_in(year!(2025), "12").unwrap(), Day(12));
+        assert_eq!(
+            Day::parse_in(year!(2025), "13").unwrap_err().to_string(),
+            "expecting a day number between 1 and 12"
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
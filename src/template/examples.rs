@@ -0,0 +1,302 @@
+/// Module that reads example manifests, and checks solutions against the examples they declare.
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::number_to_answer;
+use crate::template::output::OutputFormat;
+use crate::template::params::{with_overrides, InputKind};
+use crate::template::runner::{PartResult, RunOptions, PARSE_STAGE};
+use crate::template::{sanitise, Day, InputErrorKind, Solution, Year};
+
+/// A single example of a day, with the answers it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// Named parameters of the example, e.g. a grid size that differs from the real input.
+    pub params: HashMap<String, String>,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Example {
+    /// The expected answer of a part, if the example declares one.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The examples of a day, as declared in `data/examples/<day>.json`:
+///
+/// ```json
+/// {
+///   "examples": [
+///     { "input": "01-1.txt", "part_1": 142 },
+///     { "name": "overlapping digits", "text": "xtwone3four\n", "part_2": 24 }
+///   ]
+/// }
+/// ```
+///
+/// Inputs are either read from a file next to the manifest (`input`), or declared inline (`text`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleManifest {
+    pub examples: Vec<Example>,
+}
+
+impl ExampleManifest {
+    /// Read the manifest of a day. Returns [`None`] if the day has no manifest.
+    pub fn read(year: Year, day: Day) -> Result<Option<Self>, String> {
+        let dir = year.data_dir().join("examples");
+        let path = dir.join(format!("{day}.json"));
+
+        let Ok(s) = fs::read_to_string(&path) else {
+            return Ok(None);
+        };
+
+        Self::parse(&s, &dir)
+            .map(Some)
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Parse a manifest, reading input files from `dir`.
+    pub fn parse(s: &str, dir: &Path) -> Result<Self, String> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `examples` to be an array.")?;
+
+        Ok(Self {
+            examples: examples
+                .iter()
+                .enumerate()
+                .map(|(i, example)| parse_example(i, example, dir))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+fn parse_example(index: usize, json: &JsonValue, dir: &Path) -> Result<Example, String> {
+    let json = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or_else(|| format!("expected example {} to be an object.", index + 1))?;
+
+    let get_string = |key: &str| json.get(key).and_then(|v| v.get::<String>());
+
+    let (input, default_name) = match (get_string("input"), get_string("text")) {
+        (Some(file), None) => {
+            let input = fs::read_to_string(dir.join(file))
+                .map_err(|e| format!("could not read example input `{file}`: {e}"))?;
+            (input, file.clone())
+        }
+        (None, Some(text)) => (text.clone(), format!("example {}", index + 1)),
+        _ => {
+            return Err(format!(
+                "expected example {} to have either `input` or `text`.",
+                index + 1
+            ))
+        }
+    };
+
+    // NOTE: sanitise like `read_file` does, so that parts see the same input in both kinds of tests.
+    let input = sanitise(&input).map_err(|e| match e {
+        InputErrorKind::Placeholder => format!("example input `{default_name}` is a placeholder."),
+        _ => format!("example input `{default_name}` is empty."),
+    })?;
+
+    let params = match json.get("params") {
+        None | Some(JsonValue::Null) => HashMap::new(),
+        Some(JsonValue::Object(params)) => params
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), json_to_string(value, key)?)))
+            .collect::<Result<_, String>>()?,
+        Some(_) => return Err("expected `params` to be an object.".into()),
+    };
+
+    let get_answer = |key: &str| match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::Number(x)) => number_to_answer(*x, key).map(Some),
+        Some(value) => json_to_string(value, key).map(Some),
+    };
+
+    Ok(Example {
+        name: get_string("name").cloned().unwrap_or(default_name),
+        input,
+        params,
+        part_1: get_answer("part_1")?,
+        part_2: get_answer("part_2")?,
+    })
+}
+
+// NOTE: manifests are written by hand, so accept both strings and numbers.
+fn json_to_string(value: &JsonValue, key: &str) -> Result<String, String> {
+    match value {
+        JsonValue::String(x) => Ok(x.clone()),
+        JsonValue::Number(x) => Ok(x.to_string()),
+        JsonValue::Boolean(x) => Ok(x.to_string()),
+        _ => Err(format!("expected `{key}` to be a string or a number.")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Run every example in the manifest of a solution's day, and panic with a list of mismatches if any answer is wrong.
+/// Does nothing if the day has no manifest. The [`solution!`](crate::solution) macro generates a test that calls this.
+pub fn run_examples(solution: &Solution) {
+    let manifest = match ExampleManifest::read(solution.year, solution.day) {
+        Ok(Some(manifest)) => manifest,
+        Ok(None) => return,
+        Err(e) => panic!("{e}"),
+    };
+
+    // parts print nothing in JSON mode, the mismatches are reported below.
+    let options = RunOptions {
+        is_timed: false,
+        format: OutputFormat::Json,
+        timeout: None,
+        input: InputKind::Example,
+        ..RunOptions::default()
+    };
+
+    let mut checked = 0;
+    let mut mismatches = vec![];
+
+    for example in &manifest.examples {
+        let parts: Vec<u8> = solution
+            .parts
+            .iter()
+            .copied()
+            .filter(|part| *part != PARSE_STAGE && example.expected(*part).is_some())
+            .collect();
+
+        if parts.is_empty() {
+            continue;
+        }
+
+        checked += parts.len();
+        let results = with_overrides(&example.params, || {
+            solution.run_parts(&example.input, &parts, options)
+        });
+
+        for part in parts {
+            let expected = example.expected(part).unwrap_or_default();
+            // parts do not run if the shared parse stage failed, which is reported in their place.
+            let result = results.iter().find(|x| x.part == part);
+            let parse_failure = results
+                .iter()
+                .find(|x| x.is_parse_stage() && x.failure.is_some());
+
+            if result.and_then(|x| x.answer.as_deref()) != Some(expected) {
+                let got = match (result, parse_failure) {
+                    (Some(result), _) => describe(result),
+                    (None, Some(parse)) => format!("parsing {}", describe(parse)),
+                    (None, None) => "no result".into(),
+                };
+
+                mismatches.push(format!(
+                    "{} part {part}: expected {expected}, got {got}",
+                    example.name
+                ));
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "{} of {checked} example answers are wrong:\n{}",
+        mismatches.len(),
+        mismatches.join("\n")
+    );
+}
+
+/// Describe the outcome of a part that did not return the expected answer.
+fn describe(result: &PartResult) -> String {
+    match (&result.answer, &result.failure) {
+        (_, Some(failure)) => match failure.message() {
+            Some(message) => format!("{}: {message}", failure.status()),
+            None => failure.status().into(),
+        },
+        (Some(answer), None) => answer.clone(),
+        (None, None) => "no answer".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::ExampleManifest;
+
+    #[test]
+    fn parses_manifests() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01-1.txt"), "1abc2\n").unwrap();
+
+        let manifest = ExampleManifest::parse(
+            r#"{ "examples": [
+                { "input": "01-1.txt", "part_1": 12 },
+                { "name": "small grid", "text": "..#\n", "params": { "size": 3 }, "part_2": "abc" }
+            ] }"#,
+            &dir,
+        )
+        .unwrap();
+
+        let [first, second] = &manifest.examples[..] else {
+            panic!("expected two examples");
+        };
+
+        assert_eq!(first.name, "01-1.txt");
+        assert_eq!(first.input, "1abc2");
+        assert_eq!(first.expected(1), Some("12"));
+        assert_eq!(first.expected(2), None);
+        assert_eq!(second.name, "small grid");
+        assert_eq!(second.input, "..#");
+        assert_eq!(second.params.get("size").map(String::as_str), Some("3"));
+        assert_eq!(second.expected(2), Some("abc"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let dir = env::temp_dir();
+        assert_eq!(ExampleManifest::parse("{}", &dir).is_err(), true);
+        assert_eq!(
+            ExampleManifest::parse(r#"{ "examples": [{ "part_1": 1 }] }"#, &dir).is_err(),
+            true
+        );
+        // answers above 2^53 - 1 may be rounded when read as a number, and have to be written as a string.
+        assert_eq!(
+            ExampleManifest::parse(
+                r#"{ "examples": [{ "text": "1", "part_1": 12345678901234567890 }] }"#,
+                &dir
+            )
+            .unwrap_err()
+            .contains("as a string"),
+            true
+        );
+        assert_eq!(
+            ExampleManifest::parse(
+                r#"{ "examples": [{ "input": "missing.txt", "part_1": 1 }] }"#,
+                &dir
+            )
+            .is_err(),
+            true
+        );
+    }
+}
@@ -0,0 +1,340 @@
+/// Module that keeps an append-only history of benchmark runs.
+/// Every entry is stored as one line of JSON, together with the git commit and the machine it was recorded on.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, thread};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+static HISTORY_FILE_NAME: &str = "timings-history.ndjson";
+
+/// Describes the machine that a benchmark run was recorded on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MachineInfo {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub hostname: Option<String>,
+}
+
+impl MachineInfo {
+    pub fn current() -> Self {
+        let hostname = env::var("HOSTNAME")
+            .or_else(|_| env::var("COMPUTERNAME"))
+            .ok()
+            .or_else(|| fs::read_to_string("/etc/hostname").ok())
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty());
+
+        Self {
+            os: env::consts::OS.into(),
+            arch: env::consts::ARCH.into(),
+            cpus: thread::available_parallelism().map_or(1, usize::from),
+            hostname,
+        }
+    }
+}
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the git commit that was checked out, if any.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    pub machine: MachineInfo,
+    /// The baseline that the run was saved as, if any.
+    pub baseline: Option<String>,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create an entry for a run that happened just now.
+    pub fn new(timings: Timings, baseline: Option<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |x| x.as_secs());
+
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        Self {
+            timestamp,
+            commit: git(&["rev-parse", "--short", "HEAD"]).filter(|x| !x.is_empty()),
+            dirty: git(&["status", "--porcelain"]).is_some_and(|x| !x.is_empty()),
+            machine: MachineInfo::current(),
+            baseline,
+            timings,
+        }
+    }
+}
+
+/// Append an entry to the history file of a year.
+pub fn append(year: Year, entry: &HistoryEntry) -> Result<(), Error> {
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| Error::other(e.to_string()))?;
+
+    fs::create_dir_all(year.data_dir())?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(year.data_dir().join(HISTORY_FILE_NAME))?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all entries from the history file of a year, oldest first. If not present, returns an empty history.
+pub fn read(year: Year) -> Vec<HistoryEntry> {
+    let Ok(s) = fs::read_to_string(year.data_dir().join(HISTORY_FILE_NAME)) else {
+        return vec![];
+    };
+
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match parse_entry(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("Skipping line {} of history: {e}", i + 1);
+                None
+            }
+        })
+        .collect()
+}
+
+fn parse_entry(line: &str) -> Result<HistoryEntry, String> {
+    let json = JsonValue::from_str(line).or(Err("not valid JSON."))?;
+    HistoryEntry::try_from(&json)
+}
+
+/// Format a unix timestamp as an UTC date and time, e.g. `2023-12-01 05:00:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let secs = timestamp % 86_400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    match value {
+        Some(x) => JsonValue::String(x.clone()),
+        None => JsonValue::Null,
+    }
+}
+
+impl From<&MachineInfo> for JsonValue {
+    fn from(value: &MachineInfo) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("arch".into(), JsonValue::String(value.arch.clone()));
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cpus".into(), JsonValue::Number(value.cpus as f64));
+        map.insert("hostname".into(), optional_string(value.hostname.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MachineInfo {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected machine.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cpus = json
+            .get("cpus")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as usize)
+            .ok_or("Expected machine.cpus to be a number.")?;
+
+        Ok(MachineInfo {
+            os: get_string("os")?,
+            arch: get_string("arch")?,
+            cpus,
+            hostname: json
+                .get("hostname")
+                .and_then(|v| v.get::<String>())
+                .cloned(),
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), optional_string(value.commit.as_ref()));
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("machine".into(), JsonValue::from(&value.machine));
+        map.insert("baseline".into(), optional_string(value.baseline.as_ref()));
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let machine = json
+            .get("machine")
+            .ok_or("Expected entry to have key `machine`.")
+            .map_err(String::from)
+            .and_then(MachineInfo::try_from)?;
+
+        let timings = json
+            .get("timings")
+            .ok_or("Expected entry to have key `timings`.")
+            .map_err(String::from)
+            .and_then(Timings::try_from)?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit: json.get("commit").and_then(|v| v.get::<String>()).cloned(),
+            dirty: json
+                .get("dirty")
+                .and_then(|v| v.get::<bool>())
+                .copied()
+                .unwrap_or(false),
+            machine,
+            baseline: json
+                .get("baseline")
+                .and_then(|v| v.get::<String>())
+                .cloned(),
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, parse_entry, HistoryEntry, MachineInfo};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1_701_406_800), "2023-12-01 05:00:00");
+        assert_eq!(format_timestamp(1_709_164_799), "2024-02-28 23:59:59");
+        assert_eq!(format_timestamp(1_709_251_199), "2024-02-29 23:59:59");
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = HistoryEntry {
+            timestamp: 1_701_406_800,
+            commit: Some("abc1234".into()),
+            dirty: true,
+            machine: MachineInfo {
+                os: "linux".into(),
+                arch: "x86_64".into(),
+                cpus: 8,
+                hostname: None,
+            },
+            baseline: Some("main".into()),
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 1_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = parse_entry(&line).unwrap();
+
+        assert_eq!(parsed.timestamp, entry.timestamp);
+        assert_eq!(parsed.commit, entry.commit);
+        assert_eq!(parsed.dirty, true);
+        assert_eq!(parsed.machine, entry.machine);
+        assert_eq!(parsed.baseline, entry.baseline);
+        assert_eq!(parsed.timings.data.len(), 1);
+        assert_eq!(parsed.timings.data[0].part_1, Some("1ms".into()));
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert_eq!(parse_entry("{}").is_err(), true);
+        assert_eq!(parse_entry("not json").is_err(), true);
+    }
+}
@@ -0,0 +1,208 @@
+/// Module that reads puzzle inputs and examples, and rejects files that can not be a real input.
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::template::{Day, Year};
+
+/// Contents that end up in input files when they are saved from a browser or a failed download.
+const PLACEHOLDERS: &[&str] = &[
+    "Puzzle inputs differ by user",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+    "<!DOCTYPE html",
+    "<html",
+];
+
+/// Why an input file could not be used.
+#[derive(Debug)]
+pub enum InputErrorKind {
+    Io(io::Error),
+    /// The file is empty or only contains whitespace, e.g. because it was just scaffolded.
+    Empty,
+    /// The file contains an error page or a message of Advent of Code instead of an input.
+    Placeholder,
+}
+
+/// An input file that could not be read or does not hold an input. Names the file and how to fix it.
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub kind: InputErrorKind,
+    fix: Fix,
+}
+
+/// How to get a file that holds an input.
+#[derive(Clone, Debug)]
+enum Fix {
+    /// Run the command that downloads the file.
+    Download(String),
+    /// Copy the example from the puzzle description.
+    CopyExample,
+    /// The file was passed in by the user, who knows best.
+    None,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.display();
+        match &self.kind {
+            InputErrorKind::Io(e) => write!(f, "could not read \"{path}\": {e}.")?,
+            InputErrorKind::Empty => write!(f, "\"{path}\" is empty.")?,
+            InputErrorKind::Placeholder => {
+                write!(f, "\"{path}\" does not contain a puzzle input.")?;
+            }
+        }
+
+        match &self.fix {
+            Fix::Download(command) => write!(f, " Run `{command}` to download it."),
+            Fix::CopyExample => write!(f, " Copy the example from the puzzle description into it."),
+            Fix::None => Ok(()),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            InputErrorKind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Read a file from the `folder` of a data directory, e.g. `data/inputs/01.txt`, and sanitise it.
+pub(crate) fn read_input(
+    data_dir: &Path,
+    folder: &str,
+    file: &str,
+    year: Option<Year>,
+    day: Day,
+) -> Result<String, InputError> {
+    let path = data_dir.join(folder).join(file);
+
+    let fix = match year {
+        _ if folder != "inputs" => Fix::CopyExample,
+        Some(year) if !year.is_primary() => {
+            Fix::Download(format!("cargo download {day} --year {year}"))
+        }
+        _ => Fix::Download(format!("cargo download {day}")),
+    };
+
+    let error = |kind| InputError {
+        path: path.clone(),
+        kind,
+        fix: fix.clone(),
+    };
+
+    let contents = env::current_dir()
+        .and_then(|cwd| fs::read_to_string(cwd.join(&path)))
+        .map_err(|e| error(InputErrorKind::Io(e)))?;
+
+    sanitise(&contents).map_err(error)
+}
+
+/// Read an input that was passed on the command-line, either a path or `-` for stdin, and sanitise it.
+pub(crate) fn read_custom_input(path: &str) -> Result<String, InputError> {
+    let error = |kind| InputError {
+        path: PathBuf::from(if path == "-" { "<stdin>" } else { path }),
+        kind,
+        fix: Fix::None,
+    };
+
+    let contents = if path == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents).map(|_| contents)
+    } else {
+        fs::read_to_string(path)
+    }
+    .map_err(|e| error(InputErrorKind::Io(e)))?;
+
+    sanitise(&contents).map_err(error)
+}
+
+/// Normalise line endings to `\n` and strip trailing line breaks, so that inputs parse the same as the examples,
+/// which usually do not end with one. Fails for empty files and placeholders.
+pub fn sanitise(contents: &str) -> Result<String, InputErrorKind> {
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+    let trimmed = contents.trim_end_matches(['\r', '\n']);
+
+    if trimmed.trim().is_empty() {
+        return Err(InputErrorKind::Empty);
+    }
+
+    if PLACEHOLDERS
+        .iter()
+        .any(|placeholder| trimmed.trim_start().starts_with(placeholder))
+    {
+        return Err(InputErrorKind::Placeholder);
+    }
+
+    Ok(trimmed.replace("\r\n", "\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{sanitise, Fix, InputError, InputErrorKind};
+
+    #[test]
+    fn normalises_line_endings() {
+        assert_eq!(sanitise("1\r\n2\r\n").unwrap(), "1\n2");
+        assert_eq!(sanitise("\u{feff}1\n2").unwrap(), "1\n2");
+        assert_eq!(sanitise("1\n\n2\n\n\n").unwrap(), "1\n\n2");
+        assert_eq!(sanitise("  1 \n").unwrap(), "  1 ");
+    }
+
+    #[test]
+    fn rejects_empty_files_and_placeholders() {
+        assert!(matches!(sanitise(""), Err(InputErrorKind::Empty)));
+        assert!(matches!(sanitise(" \r\n\n"), Err(InputErrorKind::Empty)));
+        assert!(matches!(
+            sanitise("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(InputErrorKind::Placeholder)
+        ));
+        assert!(matches!(
+            sanitise("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InputErrorKind::Placeholder)
+        ));
+    }
+
+    #[test]
+    fn names_file_and_fix() {
+        let error = InputError {
+            path: PathBuf::from("data/inputs/01.txt"),
+            kind: InputErrorKind::Empty,
+            fix: Fix::Download("cargo download 01".into()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "\"data/inputs/01.txt\" is empty. Run `cargo download 01` to download it."
+        );
+
+        let error = InputError {
+            path: PathBuf::from("data/examples/01.txt"),
+            kind: InputErrorKind::Empty,
+            fix: Fix::CopyExample,
+        };
+        assert_eq!(
+            error.to_string(),
+            "\"data/examples/01.txt\" is empty. Copy the example from the puzzle description into it."
+        );
+
+        let error = InputError {
+            path: PathBuf::from("<stdin>"),
+            kind: InputErrorKind::Placeholder,
+            fix: Fix::None,
+        };
+        assert_eq!(
+            error.to_string(),
+            "\"<stdin>\" does not contain a puzzle input."
+        );
+    }
+}
@@ -1,68 +1,221 @@
-use std::{env, fs};
+use std::path::Path;
 
+pub mod answers;
 pub mod aoc_cli;
+#[cfg(feature = "http")]
+pub mod aoc_client;
+pub mod backend;
 pub mod commands;
+pub mod examples;
+pub mod output;
+pub mod reports;
 pub mod runner;
+pub mod stats;
+#[cfg(feature = "today")]
+pub mod unlock;
 
 pub use day::*;
+use input::read_input;
+pub use input::{sanitise, InputError, InputErrorKind};
+pub use params::*;
+pub use registry::*;
+pub use year::*;
 
+mod compare;
 mod day;
+mod history;
+mod input;
+mod params;
+mod puzzle;
 mod readme_benchmarks;
+mod registry;
 mod run_multi;
+mod submission;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Line endings are normalised to `\n`, and empty files or placeholders are rejected, see [`InputError`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can not be used as an input.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    read_input(Path::new("data"), folder, &format!("{day}.txt"), None, day)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_input(
+        Path::new("data"),
+        folder,
+        &format!("{day}-{part}.txt"),
+        None,
+        day,
+    )
+    .unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Same as [`read_file`], but reads from the data folder of a year, e.g. `data/2022/inputs/01.txt`.
+#[must_use]
+pub fn read_year_file(folder: &str, year: Year, day: Day) -> String {
+    try_read_year_file(folder, year, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Same as [`try_read_file`], but reads from the data folder of a year.
+pub fn try_read_year_file(folder: &str, year: Year, day: Day) -> Result<String, InputError> {
+    read_input(
+        &year.data_dir(),
+        folder,
+        &format!("{day}.txt"),
+        Some(year),
+        day,
+    )
+}
+
+/// Same as [`read_file_part`], but reads from the data folder of a year.
+#[must_use]
+pub fn read_year_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
+    read_input(
+        &year.data_dir(),
+        folder,
+        &format!("{day}-{part}.txt"),
+        Some(year),
+        day,
+    )
+    .unwrap_or_else(|e| panic!("{e}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Solutions for a year other than `AOC_YEAR` declare it as the last parameter, e.g. `solution!(1, year = 2022)`.
+///
+/// Solutions can declare a shared parse stage with `parse = <function>`, e.g. `solution!(1, parse = parse)`.
+/// The input is then parsed once, and a reference to the parsed input is passed to each part.
+/// Parsing is timed on its own and reported as a separate stage.
+///
+/// Also creates the constant `SOLUTION`, which allows the main binary to run the solution in-process,
+/// and a test that checks the solution against the example manifest of the day, see [`examples`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, parse = $parse:expr)? $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, $crate::solution!(@year $($year)?), [$($parse)?], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1 $(, parse = $parse:expr)? $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, $crate::solution!(@year $($year)?), [$($parse)?], [part_one, 1]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 2 $(, parse = $parse:expr)? $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, $crate::solution!(@year $($year)?), [$($parse)?], [part_two, 2]);
+    };
+
+    (@year) => {
+        $crate::template::Year::__parse_const(env!("AOC_YEAR"))
+    };
+    (@year $year:expr) => {
+        $crate::year!($year)
+    };
+
+    (@impl $day:expr, $year:expr, [], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@constants $day, $year, [$($part),*], |input, parts, options, report| {
+            $(
+                if parts.contains(&$part) {
+                    report($crate::template::runner::execute_part($crate::solution!(@part $func), input, YEAR, DAY, $part, options));
+                }
+            )*
+        });
+
+        #[allow(dead_code)]
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_puzzle_input(YEAR, DAY);
+            let options = RunOptions::from_args();
+            let results = vec![$( run_part($crate::solution!(@part $func), &input, YEAR, DAY, $part, options), )*];
+            $crate::solution!(@report results, options);
+        }
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+
+    (@impl $day:expr, $year:expr, [$parse:expr], $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@constants $day, $year, [$crate::template::runner::PARSE_STAGE, $($part),*], |input, parts, options, report| {
+            let (data, parse_result) = $crate::template::runner::execute_parse($parse, input, YEAR, DAY, options);
+            report(parse_result);
+            if let Some(data) = &data {
+                $(
+                    if parts.contains(&$part) {
+                        report($crate::template::runner::execute_part($crate::solution!(@part $func), data, YEAR, DAY, $part, options));
+                    }
+                )*
+            }
+        });
+
+        #[allow(dead_code)]
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_puzzle_input(YEAR, DAY);
+            let options = RunOptions::from_args();
+            let (data, parse_result) = run_parse($parse, &input, YEAR, DAY, options);
+            let mut results = vec![parse_result];
+            if let Some(data) = &data {
+                $( results.push(run_part($crate::solution!(@part $func), data, YEAR, DAY, $part, options)); )*
+            }
+            $crate::solution!(@report results, options);
+        }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@constants $day:expr, $year:expr, [$($parts:expr),*], $run:expr) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        const DAY: $crate::template::Day = $crate::day!($day, year = YEAR);
+
+        /// The year of the current day.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = $year;
+
+        /// Entry points used to run this solution in-process.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            year: YEAR,
+            day: DAY,
+            parts: &[$($parts),*],
+            run: $run,
+        };
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+        /// Checks the solution against the examples in `data/examples/<day>.json`, if present.
+        #[cfg(test)]
+        mod example_manifest {
+            #[test]
+            fn examples() {
+                $crate::template::examples::run_examples(&super::SOLUTION);
+            }
+        }
+    };
+
+    // errors that convert into a `Box<dyn Error>` are reported with their sources, see `runner::__output_kind`.
+    (@part $func:expr) => {
+        |input| {
+            #[allow(unused_imports)]
+            use $crate::template::runner::__output_kind::{ChainedKind as _, PlainKind as _};
+            let output = $func(input);
+            (&output).__output_kind().convert(output)
+        }
+    };
+
+    (@report $results:ident, $options:ident) => {
+        if $options.format == $crate::template::output::OutputFormat::Json {
+            $crate::template::output::print_json(&$results);
+        }
+        if $results.iter().any(|result| result.failure.is_some()) {
+            std::process::exit(1);
         }
     };
 }
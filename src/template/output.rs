@@ -0,0 +1,357 @@
+/// Machine-readable output of solution results.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Verdict;
+use crate::template::runner::{Failure, PartResult};
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::{Day, Year};
+
+/// The format that results are printed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable, decorated output.
+    #[default]
+    Text,
+    /// A single JSON array containing one record per part, printed once all parts are done.
+    Json,
+    /// One JSON record per line, printed as soon as a part is done.
+    Ndjson,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => f.write_str("text"),
+            OutputFormat::Json => f.write_str("json"),
+            OutputFormat::Ndjson => f.write_str("ndjson"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of the output formats `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Print a set of results as a single JSON array.
+pub fn print_json(results: &[PartResult]) {
+    let json = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+    match json.format() {
+        Ok(s) => println!("{s}"),
+        Err(e) => eprintln!("Failed to serialize results: {e}"),
+    }
+}
+
+/// Print a single result as one line of JSON.
+pub fn print_ndjson(result: &PartResult) {
+    match JsonValue::from(result).stringify() {
+        Ok(s) => println!("{s}"),
+        Err(e) => eprintln!("Failed to serialize result: {e}"),
+    }
+}
+
+/// Parse a line of NDJSON output into a result. Returns `None` for lines that are not records.
+pub fn parse_ndjson(line: &str) -> Option<PartResult> {
+    let json = JsonValue::from_str(line).ok()?;
+    PartResult::try_from(&json).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.year.into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = value.duration.as_nanos() as f64;
+        map.insert("duration_nanos".into(), JsonValue::Number(nanos));
+        map.insert(
+            "duration".into(),
+            JsonValue::String(format!("{:.1?}", value.duration)),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value.samples as f64;
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert("status".into(), JsonValue::String(value.status().into()));
+        map.insert(
+            "error".into(),
+            match value.failure.as_ref().and_then(Failure::message) {
+                Some(x) => JsonValue::String(x.into()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected result.{key} to be a number."))
+        };
+
+        // records without a year belong to the primary year.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = match json.get("year") {
+            Some(_) => Year::new(get_number("year")? as u16),
+            None => Year::primary(),
+        }
+        .ok_or("Expected result.year to be a Year.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Day::new_in(year, get_number("day")? as u8)
+            .ok_or("Expected result.day to be a Day.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = get_number("part")? as u8;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = Duration::from_nanos(get_number("duration_nanos")? as u64);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = get_number("samples")? as u128;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(x) => x.parse::<Verdict>()?,
+            None => Verdict::Unknown,
+        };
+
+        let error = json
+            .get("error")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .unwrap_or_default();
+
+        let failure = match json.get("status").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "panicked" => Some(Failure::Panicked(error)),
+            Some(x) if x == "timed_out" => Some(Failure::TimedOut),
+            Some(x) if x == "failed" => Some(Failure::Failed(error)),
+            _ => None,
+        };
+
+        Ok(PartResult {
+            year,
+            day,
+            part,
+            answer: answer.cloned(),
+            duration,
+            samples,
+            stats,
+            memory,
+            verdict,
+            failure,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_ndjson, OutputFormat};
+    use crate::{
+        day,
+        template::{
+            answers::Verdict,
+            runner::{Failure, PartResult},
+            stats::{BenchStats, MemoryStats},
+        },
+        year,
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "ndjson".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert_eq!("xml".parse::<OutputFormat>().is_err(), true);
+    }
+
+    #[test]
+    fn roundtrips_results() {
+        let result = PartResult {
+            year: year!(2022),
+            day: day!(4),
+            part: 2,
+            answer: Some("(74) @ 2".into()),
+            duration: Duration::from_nanos(74_130),
+            samples: 10_000,
+            stats: BenchStats::from_samples(&[Duration::from_nanos(74_130)], 100),
+            memory: Some(MemoryStats {
+                peak_bytes: 1024,
+                total_allocations: 3,
+                total_bytes: 2048,
+            }),
+            verdict: Verdict::Correct,
+            failure: None,
+        };
+
+        let line = JsonValue::from(&result).stringify().unwrap();
+        let parsed = parse_ndjson(&line).unwrap();
+
+        assert_eq!(parsed.year, year!(2022));
+        assert_eq!(parsed.day, day!(4));
+        assert_eq!(parsed.part, 2);
+        assert_eq!(parsed.answer, Some("(74) @ 2".into()));
+        assert_eq!(parsed.duration, Duration::from_nanos(74_130));
+        assert_eq!(parsed.samples, 10_000);
+        assert_eq!(parsed.stats, result.stats);
+        assert_eq!(parsed.memory, result.memory);
+        assert_eq!(parsed.verdict, Verdict::Correct);
+    }
+
+    #[test]
+    fn serializes_unsolved_results() {
+        let result = PartResult {
+            year: year!(2023),
+            day: day!(1),
+            part: 1,
+            answer: None,
+            duration: Duration::ZERO,
+            samples: 1,
+            stats: None,
+            memory: None,
+            verdict: Verdict::Unknown,
+            failure: None,
+        };
+
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(line.contains(r#""answer":null"#), true);
+        assert_eq!(line.contains(r#""status":"unsolved""#), true);
+        assert_eq!(line.contains(r#""verdict":"unknown""#), true);
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        let result = PartResult {
+            year: year!(2023),
+            day: day!(5),
+            part: 1,
+            answer: None,
+            duration: Duration::from_millis(10),
+            samples: 1,
+            stats: None,
+            memory: None,
+            verdict: Verdict::Unknown,
+            failure: Some(Failure::Panicked("attempt to add with overflow".into())),
+        };
+
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(line.contains(r#""status":"panicked""#), true);
+        assert_eq!(
+            parse_ndjson(&line).unwrap().failure,
+            Some(Failure::Panicked("attempt to add with overflow".into()))
+        );
+
+        let timed_out = PartResult {
+            failure: Some(Failure::TimedOut),
+            ..result
+        };
+        let line = JsonValue::from(&timed_out).stringify().unwrap();
+        assert_eq!(line.contains(r#""error":null"#), true);
+        assert_eq!(
+            parse_ndjson(&line).unwrap().failure,
+            Some(Failure::TimedOut)
+        );
+    }
+
+    #[test]
+    fn ignores_non_record_lines() {
+        assert_eq!(parse_ndjson("debug output").is_none(), true);
+        assert_eq!(parse_ndjson("[1, 2]").is_none(), true);
+    }
+}
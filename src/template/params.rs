@@ -0,0 +1,145 @@
+/// Module for named parameters, whose values differ between the examples and the real input.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// The kind of input that a solution part runs against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputKind {
+    /// The puzzle input. This is what the runner uses.
+    #[default]
+    Real,
+    /// An example from the puzzle description. This is what tests use, unless they run via the runner.
+    Example,
+}
+
+/// A named parameter of a solution, e.g. the number of steps to walk, which is smaller in the examples.
+///
+/// ```
+/// # use advent_of_code::template::Param;
+/// const STEPS: Param<usize> = Param::new("steps", 64, 6);
+///
+/// // tests that call a part directly run against examples.
+/// assert_eq!(STEPS.get(), 6);
+/// ```
+///
+/// Parts that are run by the runner, e.g. via `cargo solve`, get the `real` value. Parts that are called directly,
+/// e.g. in tests, get the `example` value. Examples in a manifest can override a parameter by its name.
+#[derive(Clone, Copy, Debug)]
+pub struct Param<T> {
+    pub name: &'static str,
+    pub real: T,
+    pub example: T,
+}
+
+impl<T: Copy + FromStr> Param<T>
+where
+    T::Err: Debug,
+{
+    pub const fn new(name: &'static str, real: T, example: T) -> Self {
+        Self {
+            name,
+            real,
+            example,
+        }
+    }
+
+    /// The value of the parameter for the input that is currently run against.
+    pub fn get(&self) -> T {
+        CONTEXT.with_borrow(|context| match context.overrides.get(self.name) {
+            Some(value) => value.parse().unwrap_or_else(|e| {
+                panic!(
+                    "invalid value `{value}` for parameter `{}`: {e:?}",
+                    self.name
+                )
+            }),
+            None if context.kind == Some(InputKind::Real) => self.real,
+            None => self.example,
+        })
+    }
+}
+
+#[derive(Default)]
+struct Context {
+    // NOTE: no kind means that a part was called directly, which is the case in tests.
+    kind: Option<InputKind>,
+    overrides: HashMap<String, String>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::default();
+}
+
+/// Restores the previous context of the thread when dropped, also if the scoped function panics.
+struct Restore<T: 'static> {
+    previous: Option<T>,
+    set: fn(&mut Context, T),
+}
+
+impl<T: 'static> Drop for Restore<T> {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            CONTEXT.with_borrow_mut(|context| (self.set)(context, previous));
+        }
+    }
+}
+
+/// Run `f` against the given kind of input, i.e. with the matching parameter values.
+pub(crate) fn with_input_kind<R>(kind: InputKind, f: impl FnOnce() -> R) -> R {
+    let previous = CONTEXT.with_borrow_mut(|context| context.kind.replace(kind));
+    let _restore = Restore {
+        previous: Some(previous),
+        set: |context, previous| context.kind = previous,
+    };
+    f()
+}
+
+/// Run `f` with parameters overridden by name, e.g. by the `params` of an example in a manifest.
+pub(crate) fn with_overrides<R>(overrides: &HashMap<String, String>, f: impl FnOnce() -> R) -> R {
+    let previous = CONTEXT
+        .with_borrow_mut(|context| std::mem::replace(&mut context.overrides, overrides.clone()));
+    let _restore = Restore {
+        previous: Some(previous),
+        set: |context, previous| context.overrides = previous,
+    };
+    f()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{with_input_kind, with_overrides, InputKind, Param};
+
+    const STEPS: Param<usize> = Param::new("steps", 64, 6);
+
+    #[test]
+    fn selects_values_by_input_kind() {
+        assert_eq!(STEPS.get(), 6);
+        assert_eq!(with_input_kind(InputKind::Real, || STEPS.get()), 64);
+        assert_eq!(with_input_kind(InputKind::Example, || STEPS.get()), 6);
+        assert_eq!(STEPS.get(), 6);
+    }
+
+    #[test]
+    fn overrides_values_by_name() {
+        let overrides = HashMap::from([("steps".to_string(), "10".to_string())]);
+        assert_eq!(with_overrides(&overrides, || STEPS.get()), 10);
+        assert_eq!(
+            with_overrides(&overrides, || with_input_kind(InputKind::Real, || STEPS
+                .get())),
+            10
+        );
+        assert_eq!(STEPS.get(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_overrides() {
+        let overrides = HashMap::from([("steps".to_string(), "many".to_string())]);
+        with_overrides(&overrides, || STEPS.get());
+    }
+}
@@ -0,0 +1,204 @@
+/// The example of a part, as found in its description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    pub part: u8,
+    /// The first code block of the part. Part two often has none, as it reuses the example of part one.
+    pub input: Option<String>,
+    /// The last emphasised code in the part, e.g. `` `*142*` ``, which is the answer for the example by convention.
+    pub answer: Option<String>,
+}
+
+/// Find the examples of every part in the markdown of a puzzle description, as written by `cargo download`.
+/// Part two is only present once it is unlocked.
+pub fn parse_examples(markdown: &str) -> Vec<PartExample> {
+    split_parts(markdown)
+        .into_iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let (blocks, text) = split_code_blocks(section);
+            PartExample {
+                part: u8::try_from(i + 1).unwrap_or(u8::MAX),
+                input: blocks.into_iter().find(|block| !block.trim().is_empty()),
+                answer: find_emphasised_code(&text).pop(),
+            }
+        })
+        .collect()
+}
+
+/// Split a description into the sections of its parts, at the `--- Part Two ---` heading.
+fn split_parts(markdown: &str) -> Vec<&str> {
+    let heading = markdown.match_indices("\n## ").find(|(i, _)| {
+        let line = markdown[i + 1..].lines().next().unwrap_or_default();
+        line.contains("Part Two")
+    });
+
+    match heading {
+        Some((i, _)) => vec![&markdown[..i], &markdown[i + 1..]],
+        None => vec![markdown],
+    }
+}
+
+/// Separate the code blocks of a section from its text. Both fenced blocks and blocks indented by four spaces count.
+fn split_code_blocks(section: &str) -> (Vec<String>, String) {
+    let mut blocks: Vec<String> = vec![];
+    let mut text = String::new();
+    let mut fenced: Option<String> = None;
+    let mut indented: Option<String> = None;
+    let mut previous_blank = true;
+
+    for line in section.lines() {
+        if let Some(block) = &mut fenced {
+            if line.trim_start().starts_with("```") {
+                blocks.extend(fenced.take());
+            } else {
+                block.push_str(line);
+                block.push('\n');
+            }
+            continue;
+        }
+
+        if line.trim_start().starts_with("```") {
+            blocks.extend(indented.take());
+            fenced = Some(String::new());
+            continue;
+        }
+
+        let code = line
+            .strip_prefix("    ")
+            .or_else(|| line.strip_prefix('\t'));
+
+        match (code, &mut indented) {
+            (Some(code), Some(block)) => {
+                block.push_str(code);
+                block.push('\n');
+            }
+            (Some(code), None) if previous_blank => indented = Some(format!("{code}\n")),
+            _ => {
+                blocks.extend(indented.take());
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+
+        previous_blank = line.trim().is_empty();
+    }
+
+    blocks.extend(fenced);
+    blocks.extend(indented);
+    (blocks, text)
+}
+
+/// Find inline code that is emphasised, e.g. `` `*142*` `` or ``*`142`*``, in order.
+fn find_emphasised_code(text: &str) -> Vec<String> {
+    let mut found = vec![];
+
+    for line in text.lines() {
+        let mut rest = line;
+        while let Some(start) = rest.find('`') {
+            let Some(len) = rest[start + 1..].find('`') else {
+                break;
+            };
+            let code = &rest[start + 1..start + 1 + len];
+            let end = start + len + 2;
+
+            let is_wrapped = rest[..start].ends_with('*') && rest[end..].starts_with('*');
+            let inner = code
+                .strip_prefix('*')
+                .and_then(|x| x.strip_suffix('*'))
+                .filter(|x| !x.is_empty());
+
+            match inner {
+                Some(inner) => found.push(inner.to_string()),
+                None if is_wrapped && !code.is_empty() => found.push(code.to_string()),
+                None => {}
+            }
+
+            rest = &rest[end..];
+        }
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_emphasised_code, parse_examples, PartExample};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these lines are `12` and `38`. Adding these together produces `*50*`.
+
+What is the sum of all of the calibration values?
+
+## --- Part Two ---
+
+For example:
+
+```
+two1nine
+eightwothree
+```
+
+In this example, the calibration values are `29` and `83`. Adding these together produces `*112*`.
+";
+
+    #[test]
+    fn finds_examples_of_both_parts() {
+        assert_eq!(
+            parse_examples(PUZZLE),
+            vec![
+                PartExample {
+                    part: 1,
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("50".into()),
+                },
+                PartExample {
+                    part: 2,
+                    input: Some("two1nine\neightwothree\n".into()),
+                    answer: Some("112".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_locked_part_two_and_indented_blocks() {
+        let puzzle =
+            "## --- Day 9 ---\n\nFor example:\n\n    0 3 6\n    1 3 6\n\nThe sum is *`114`*.\n";
+
+        assert_eq!(
+            parse_examples(puzzle),
+            vec![PartExample {
+                part: 1,
+                input: Some("0 3 6\n1 3 6\n".into()),
+                answer: Some("114".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn handles_parts_without_examples() {
+        let puzzle = "## --- Day 6 ---\n\n```\nTime: 7\n```\n\nThat makes `*288*`.\n\n## --- Part Two ---\n\nNow there is one race, you could win in `*71503*` ways.\n";
+        let examples = parse_examples(puzzle);
+
+        assert_eq!(examples[1].input, None);
+        assert_eq!(examples[1].answer, Some("71503".into()));
+    }
+
+    #[test]
+    fn finds_emphasised_code() {
+        assert_eq!(
+            find_emphasised_code("values `12`, *`13`* and `*142*`, or `**`.\n`*a b*`"),
+            vec!["13", "142", "a b"]
+        );
+    }
+}
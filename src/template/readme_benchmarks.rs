@@ -1,11 +1,14 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt::Display, fs, io};
 
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::stats::{format_bytes, BenchStats, MemoryStats};
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::Year;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
 
 #[derive(Debug)]
 pub enum Error {
@@ -19,18 +22,42 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Which figure to show for each part in the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TableStyle {
+    /// The mean of all samples.
+    #[default]
+    Mean,
+    /// The median of all samples, plus / minus their standard deviation.
+    /// Falls back to the mean for timings that were stored without statistics.
+    Median,
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// The marker of the table of a year. The primary year uses the plain marker, other years carry the year.
+fn get_marker(year: Year) -> String {
+    if year.is_primary() {
+        MARKER.into()
+    } else {
+        format!("{MARKER_PREFIX} {year} --->")
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,55 +78,183 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+pub(crate) fn format_cell(
+    mean: Option<String>,
+    stats: Option<BenchStats>,
+    style: TableStyle,
+) -> String {
+    match (style, stats) {
+        (TableStyle::Median, Some(stats)) => {
+            format!("{:.1?} ± {:.1?}", stats.median, stats.std_dev)
+        }
+        _ => mean.unwrap_or_else(|| "-".into()),
+    }
+}
+
+fn format_memory_cell(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(|| "-".into(), |x| format_bytes(x.peak_bytes))
+}
+
+/// Add an empty table for a year that has none yet, after the last table of any other year.
+fn insert_table(readme: &mut String, year: Year) -> Result<(), Error> {
+    let pos = readme
+        .rfind(MARKER_PREFIX)
+        .and_then(|start| readme[start..].find("--->").map(|end| start + end + 4))
+        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+
+    let marker = get_marker(year);
+    readme.insert_str(pos, &format!("\n\n{marker}\n{marker}"));
+    Ok(())
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    style: TableStyle,
+) -> String {
+    let marker = get_marker(year);
+    let header = if year.is_primary() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+    lines.extend(table_lines(year, timings, total_millis, style, true));
+    lines.push(marker);
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    lines.join("\n")
+}
+
+/// A standalone markdown report of the timings of a year, with the same table as the readme.
+/// Days do not link to their solutions, as the report may be published without the sources.
+pub(crate) fn markdown_report(year: Year, timings: Timings, style: TableStyle) -> String {
+    let total_millis = timings.total_millis();
+    let mut lines = vec![format!("# {year} Benchmarks"), String::new()];
+    lines.extend(table_lines(year, timings, total_millis, style, false));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// The benchmark table and the total, without markers or a header.
+fn table_lines(
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    style: TableStyle,
+    link_days: bool,
+) -> Vec<String> {
+    // the parse stage gets its own column once any solution declares one.
+    let has_parse = timings.data.iter().any(|timing| timing.parse.is_some());
+    // the peak memory of each part gets its own column once it was recorded with `--memory`.
+    let has_memory = timings
+        .data
+        .iter()
+        .any(|timing| timing.part_1_memory.is_some() || timing.part_2_memory.is_some());
+
+    let mut lines: Vec<String> = vec![];
+
+    match (has_parse, has_memory) {
+        (true, true) => {
+            lines.push("| Day | Parse | Part 1 | Part 2 | Part 1 memory | Part 2 memory |".into());
+            lines.push("| :---: | :---: | :---: | :---: | :---: | :---:  |".into());
+        }
+        (true, false) => {
+            lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+            lines.push("| :---: | :---: | :---: | :---:  |".into());
+        }
+        (false, true) => {
+            lines.push("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |".into());
+            lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+        }
+        (false, false) => {
+            lines.push("| Day | Part 1 | Part 2 |".into());
+            lines.push("| :---: | :---: | :---:  |".into());
+        }
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let day = if link_days {
+            format!(
+                "[Day {}]({})",
+                timing.day.into_inner(),
+                get_path_for_bin(year, timing.day)
+            )
+        } else {
+            format!("Day {}", timing.day.into_inner())
+        };
+        let parse = if has_parse {
+            format!(
+                " `{}` |",
+                format_cell(timing.parse, timing.parse_stats, style)
+            )
+        } else {
+            String::new()
+        };
+        let memory = if has_memory {
+            format!(
+                " `{}` | `{}` |",
+                format_memory_cell(timing.part_1_memory),
+                format_memory_cell(timing.part_2_memory)
+            )
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "| {day} |{parse} `{}` | `{}` |{memory}",
+            format_cell(timing.part_1, timing.part_1_stats, style),
+            format_cell(timing.part_2, timing.part_2_stats, style),
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
-
-    lines.join("\n")
+    lines
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+    style: TableStyle,
+) -> Result<(), Error> {
+    let marker = get_marker(year);
+    if !year.is_primary() && !s.contains(&marker) {
+        insert_table(s, year)?;
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", year, timings, total_millis, style);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmark table of a year in the readme. Tables of other years are left untouched.
+pub fn update(year: Year, timings: Timings, style: TableStyle) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis, style)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, TableStyle, MARKER};
+    use std::time::Duration;
+
+    use crate::{
+        day,
+        template::stats::{BenchStats, MemoryStats},
+        template::timings::Timing,
+        template::timings::Timings,
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +264,39 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 },
             ],
         }
@@ -130,28 +306,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,7 +370,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -179,4 +397,103 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_median_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(BenchStats {
+            median: Duration::from_micros(9_500),
+            std_dev: Duration::from_micros(250),
+            ..BenchStats::default()
+        });
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 190.0, TableStyle::Median).unwrap();
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `9.5ms ± 250.0µs` | `20ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_parse_stage_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 190.0, TableStyle::Mean).unwrap();
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_memory_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_memory = Some(MemoryStats {
+            peak_bytes: 1536,
+            total_allocations: 3,
+            total_bytes: 4096,
+        });
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 190.0, TableStyle::Mean).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `-` | `1.5 KiB` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn adds_tables_for_other_years() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            year!(2023),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2022),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            year!(2022),
+            get_mock_timings(),
+            190.0,
+            TableStyle::Mean,
+        )
+        .unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2022 --->").count(), 2);
+        assert_eq!(s.matches("## 2022 Benchmarks").count(), 1);
+        assert_eq!(s.contains("| [Day 1](./src/bin/2022-01.rs) |"), true);
+        assert_eq!(
+            s.find("## Benchmarks").unwrap() < s.find("## 2022 Benchmarks").unwrap(),
+            true
+        );
+        assert_eq!(s.ends_with("<!--- benchmarking table 2022 --->\nbaz"), true);
+    }
 }
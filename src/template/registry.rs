@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::template::runner::{run_with_timeout, PartResult, RunOptions, PARSE_STAGE};
+use crate::template::{try_read_year_file, Day, InputError, Year};
+
+/// A type-erased solution. Takes the puzzle input, the parts to run and the options to run them with,
+/// and reports the result of every stage as soon as it is done.
+///
+/// Solutions with a shared parse stage parse the input once, report it as [`PARSE_STAGE`] and then run
+/// the parts against the parsed input. If parsing fails, no part is run.
+pub type DayFn = fn(&str, &[u8], RunOptions, &mut dyn FnMut(PartResult));
+
+/// Entry points into a day's solution, as declared by the [`solution!`](crate::solution) macro.
+///
+/// The main binary keeps a registry of these so that `all` and `time` can execute solutions
+/// without invoking `cargo run` for every day.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// The stages of the solution in the order they run, starting with [`PARSE_STAGE`] if it declares one.
+    pub parts: &'static [u8],
+    pub run: DayFn,
+}
+
+impl Solution {
+    /// Run every part of the solution against the day's puzzle input.
+    pub fn run(&self, options: RunOptions) -> Result<Vec<PartResult>, InputError> {
+        let input = try_read_year_file("inputs", self.year, self.day)?;
+        Ok(self.run_parts(&input, self.parts, options))
+    }
+
+    /// Run the given parts of the solution against an input.
+    /// With a timeout, the solution runs on its own thread so that a stage can be given up on.
+    pub fn run_parts(&self, input: &str, parts: &[u8], options: RunOptions) -> Vec<PartResult> {
+        if let Some(timeout) = options.timeout {
+            let input: Arc<str> = input.into();
+            return run_with_timeout(self, &input, parts, options, timeout);
+        }
+
+        let mut results = vec![];
+        (self.run)(input, parts, options, &mut |result| results.push(result));
+        results
+    }
+
+    pub fn has_parse_stage(&self) -> bool {
+        self.parts.contains(&PARSE_STAGE)
+    }
+}
+
+/// Look up the solution for a given year and day in a registry.
+pub fn find_solution(solutions: &[Solution], year: Year, day: Day) -> Option<&Solution> {
+    solutions
+        .iter()
+        .find(|solution| solution.year == year && solution.day == day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::template::output::OutputFormat;
+    use crate::template::runner::{Failure, RunOptions, PARSE_STAGE};
+
+    mod parsed {
+        use super::PARSES;
+        use std::sync::atomic::Ordering;
+
+        crate::solution!(3, parse = parse);
+
+        fn parse(input: &str) -> Vec<u32> {
+            PARSES.fetch_add(1, Ordering::SeqCst);
+            input.lines().map(|x| x.parse().unwrap()).collect()
+        }
+
+        pub fn part_one(data: &[u32]) -> Option<u32> {
+            Some(data.iter().sum())
+        }
+
+        pub fn part_two(data: &[u32]) -> Option<u32> {
+            data.iter().copied().max()
+        }
+    }
+
+    mod failing {
+        use std::fmt::Display;
+
+        crate::solution!(4, 1);
+
+        /// An error that only implements [`Display`].
+        pub struct NotANumber;
+
+        impl Display for NotANumber {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("not a number")
+            }
+        }
+
+        pub fn part_one(input: &str) -> Result<u32, NotANumber> {
+            input.parse().map_err(|_| NotANumber)
+        }
+    }
+
+    static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+    #[test]
+    fn parses_input_once() {
+        let options = RunOptions {
+            format: OutputFormat::Json,
+            ..RunOptions::default()
+        };
+        let solution = parsed::SOLUTION;
+
+        let results = solution.run_parts("1\n5\n3", solution.parts, options);
+
+        assert_eq!(PARSES.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            results.iter().map(|x| x.part).collect::<Vec<_>>(),
+            vec![PARSE_STAGE, 1, 2]
+        );
+        assert_eq!(results[1].answer.as_deref(), Some("9"));
+        assert_eq!(results[2].answer.as_deref(), Some("5"));
+
+        // with a timeout, the solution runs on its own thread, and still parses once.
+        let options = RunOptions {
+            timeout: Some(Duration::from_secs(10)),
+            ..options
+        };
+        let results = solution.run_parts("1\n5\n3", &[2], options);
+
+        assert_eq!(PARSES.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            results.iter().map(|x| x.part).collect::<Vec<_>>(),
+            vec![PARSE_STAGE, 2]
+        );
+        assert_eq!(results[1].answer.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn reports_display_errors() {
+        let options = RunOptions {
+            format: OutputFormat::Json,
+            ..RunOptions::default()
+        };
+        let solution = failing::SOLUTION;
+
+        let results = solution.run_parts("abc", solution.parts, options);
+
+        assert_eq!(
+            results[0].failure,
+            Some(Failure::Failed("not a number".into()))
+        );
+    }
+}
@@ -0,0 +1,549 @@
+/// Module that renders timings as standalone reports, which can be published as static files.
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::readme_benchmarks::{format_cell, markdown_report, TableStyle};
+use crate::template::runner::PARSE_STAGE;
+use crate::template::stats::{format_bytes, BenchStats};
+use crate::template::timings::{Timing, Timings};
+use crate::template::Year;
+
+/// Format of a benchmark report, written with `cargo time --report <format>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// One row per stage of a day, with durations in nanoseconds.
+    Csv,
+    /// A page with a table that sorts by the clicked column.
+    Html,
+    /// A horizontal bar chart of the time of every stage, on a log scale.
+    Svg,
+    /// The benchmark table of the readme.
+    Md,
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Html => "html",
+            ReportFormat::Svg => "svg",
+            ReportFormat::Md => "md",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = ReportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ReportFormat::Csv),
+            "html" => Ok(ReportFormat::Html),
+            "svg" => Ok(ReportFormat::Svg),
+            "md" => Ok(ReportFormat::Md),
+            _ => Err(ReportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ReportFormat`].
+#[derive(Debug)]
+pub struct ReportFormatFromStrError;
+
+impl Error for ReportFormatFromStrError {}
+
+impl Display for ReportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of the report formats `csv`, `html`, `svg` or `md`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Render a report of the timings of a year.
+pub fn render(format: ReportFormat, year: Year, timings: &Timings, style: TableStyle) -> String {
+    match format {
+        ReportFormat::Csv => render_csv(timings),
+        ReportFormat::Html => render_html(year, timings, style),
+        ReportFormat::Svg => render_svg(year, timings),
+        ReportFormat::Md => markdown_report(year, timings.clone(), style),
+    }
+}
+
+/// Write a report of the timings of a year to the `reports` folder of its data directory, e.g. `data/reports/benchmarks.svg`.
+/// Returns the path of the written file.
+pub fn write(
+    format: ReportFormat,
+    year: Year,
+    timings: &Timings,
+    style: TableStyle,
+) -> io::Result<PathBuf> {
+    let dir = year.data_dir().join("reports");
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("benchmarks.{format}"));
+    fs::write(&path, render(format, year, timings, style))?;
+    Ok(path)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The stages of a day and their names.
+const STAGES: [(u8, &str); 3] = [(PARSE_STAGE, "Parse"), (1, "Part 1"), (2, "Part 2")];
+
+/// Stages shown in a report. The parse stage is left out unless a solution declares one.
+fn stages(timings: &Timings) -> Vec<(u8, &'static str)> {
+    STAGES
+        .into_iter()
+        .filter(|(part, _)| *part != PARSE_STAGE || timings.data.iter().any(|t| t.parse.is_some()))
+        .collect()
+}
+
+/// The stored mean of a stage, e.g. `45.2µs`.
+fn mean_label(timing: &Timing, part: u8) -> Option<String> {
+    match part {
+        PARSE_STAGE => timing.parse.clone(),
+        1 => timing.part_1.clone(),
+        2 => timing.part_2.clone(),
+        _ => None,
+    }
+}
+
+/// Escape text for use in HTML and SVG documents.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_csv(timings: &Timings) -> String {
+    let mut lines =
+        vec!["day,stage,mean_ns,median_ns,std_dev_ns,min_ns,max_ns,peak_bytes".to_string()];
+
+    for timing in &timings.data {
+        for (part, _) in STAGES {
+            let Some(mean) = timing.mean_nanos(part) else {
+                continue;
+            };
+
+            let stage = if part == PARSE_STAGE {
+                "parse".into()
+            } else {
+                format!("part_{part}")
+            };
+            let stat = |f: fn(&BenchStats) -> Duration| {
+                timing
+                    .stats(part)
+                    .map(|x| f(x).as_nanos().to_string())
+                    .unwrap_or_default()
+            };
+            let peak = timing
+                .memory(part)
+                .map(|x| x.peak_bytes.to_string())
+                .unwrap_or_default();
+
+            lines.push(format!(
+                "{},{stage},{mean:.0},{},{},{},{},{peak}",
+                timing.day.into_inner(),
+                stat(|x| x.median),
+                stat(|x| x.std_dev),
+                stat(|x| x.min),
+                stat(|x| x.max),
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>%TITLE%</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2rem; }
+table { border-collapse: collapse; font-variant-numeric: tabular-nums; }
+th, td { padding: 0.25rem 0.75rem; text-align: right; border-bottom: 1px solid #ddd; }
+th:first-child, td:first-child { text-align: left; }
+th { cursor: pointer; user-select: none; }
+th[data-order="asc"]::after { content: " ▲"; }
+th[data-order="desc"]::after { content: " ▼"; }
+tfoot td { font-weight: bold; border-bottom: none; }
+</style>
+</head>
+<body>
+<h1>%TITLE%</h1>
+<table>
+<thead>
+<tr>%HEADER%</tr>
+</thead>
+<tbody>
+%ROWS%
+</tbody>
+<tfoot>
+<tr><td colspan="%COLUMNS%">Total: %TOTAL%</td></tr>
+</tfoot>
+</table>
+<script>
+// sort by the clicked column, using the raw value of each cell. Cells without a value sort last.
+document.querySelectorAll("thead th").forEach((th, column) => {
+  th.addEventListener("click", () => {
+    const body = th.closest("table").tBodies[0];
+    const ascending = th.dataset.order !== "asc";
+    th.parentElement.querySelectorAll("th").forEach((x) => delete x.dataset.order);
+    th.dataset.order = ascending ? "asc" : "desc";
+
+    const value = (row) => {
+      const x = parseFloat(row.cells[column].dataset.value);
+      return Number.isNaN(x) ? Infinity : x;
+    };
+
+    [...body.rows]
+      .sort((a, b) => (ascending ? 1 : -1) * (value(a) - value(b) || 0))
+      .forEach((row) => body.append(row));
+  });
+});
+</script>
+</body>
+</html>
+"#;
+
+fn render_html(year: Year, timings: &Timings, style: TableStyle) -> String {
+    let stages = stages(timings);
+    let has_memory = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
+    let memory_parts: &[u8] = if has_memory { &[1, 2] } else { &[] };
+
+    let mut header = vec!["<th>Day</th>".to_string()];
+    header.extend(stages.iter().map(|(_, name)| format!("<th>{name}</th>")));
+    header.extend(
+        memory_parts
+            .iter()
+            .map(|part| format!("<th>Part {part} memory</th>")),
+    );
+
+    let cell = |value: Option<String>, label: String| match value {
+        Some(value) => format!("<td data-value=\"{value}\">{}</td>", escape(&label)),
+        None => "<td>-</td>".into(),
+    };
+
+    let rows: Vec<String> = timings
+        .data
+        .iter()
+        .map(|timing| {
+            let day = timing.day.into_inner();
+            let mut cells = vec![format!("<td data-value=\"{day}\">Day {day}</td>")];
+
+            cells.extend(stages.iter().map(|(part, _)| {
+                cell(
+                    timing.mean_nanos(*part).map(|x| format!("{x:.0}")),
+                    format_cell(
+                        mean_label(timing, *part),
+                        timing.stats(*part).copied(),
+                        style,
+                    ),
+                )
+            }));
+
+            cells.extend(memory_parts.iter().map(|part| {
+                let memory = timing.memory(*part);
+                cell(
+                    memory.map(|x| x.peak_bytes.to_string()),
+                    memory
+                        .map(|x| format_bytes(x.peak_bytes))
+                        .unwrap_or_default(),
+                )
+            }));
+
+            format!("<tr>{}</tr>", cells.join(""))
+        })
+        .collect();
+
+    HTML_TEMPLATE
+        .replace("%TITLE%", &format!("{year} Benchmarks"))
+        .replace("%HEADER%", &header.join(""))
+        .replace("%ROWS%", &rows.join("\n"))
+        .replace("%COLUMNS%", &header.len().to_string())
+        .replace("%TOTAL%", &format!("{:.2}ms", timings.total_millis()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+const SVG_WIDTH: f64 = 800.0;
+/// Space left of the bars for the day labels.
+const SVG_LABEL_WIDTH: f64 = 70.0;
+/// Space right of the bars for the value of the longest bar.
+const SVG_VALUE_WIDTH: f64 = 80.0;
+/// Space above the bars for the title and the legend.
+const SVG_HEADER_HEIGHT: f64 = 64.0;
+/// Space below the bars for the labels of the axis.
+const SVG_AXIS_HEIGHT: f64 = 28.0;
+const SVG_BAR_HEIGHT: f64 = 14.0;
+const SVG_BAR_GAP: f64 = 2.0;
+const SVG_DAY_GAP: f64 = 10.0;
+
+fn stage_color(part: u8) -> &'static str {
+    match part {
+        PARSE_STAGE => "#9e9e9e",
+        1 => "#5b8def",
+        _ => "#f4a340",
+    }
+}
+
+/// Powers of ten that enclose every duration in nanoseconds, so that the axis starts and ends on a full unit.
+fn log_bounds(nanos: impl Iterator<Item = f64>) -> (i32, i32) {
+    let (min, max) = nanos.fold((f64::INFINITY, 0_f64), |(min, max), x| {
+        (min.min(x.max(1.0)), max.max(x.max(1.0)))
+    });
+
+    if min > max {
+        return (0, 1);
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (low, high) = (min.log10().floor() as i32, max.log10().ceil() as i32);
+    (low, high.max(low + 1))
+}
+
+/// Position of a duration on a log scale between two powers of ten, from 0 to 1.
+fn log_position(nanos: f64, (low, high): (i32, i32)) -> f64 {
+    (nanos.max(1.0).log10() - f64::from(low)) / f64::from(high - low)
+}
+
+fn render_svg(year: Year, timings: &Timings) -> String {
+    let stages = stages(timings);
+    let bounds = log_bounds(
+        timings
+            .data
+            .iter()
+            .flat_map(|t| stages.iter().filter_map(|(part, _)| t.mean_nanos(*part))),
+    );
+
+    let chart_width = SVG_WIDTH - SVG_LABEL_WIDTH - SVG_VALUE_WIDTH;
+    #[allow(clippy::cast_precision_loss)]
+    let day_height = stages.len() as f64 * (SVG_BAR_HEIGHT + SVG_BAR_GAP) + SVG_DAY_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let chart_height = timings.data.len() as f64 * day_height;
+    let height = SVG_HEADER_HEIGHT + chart_height + SVG_AXIS_HEIGHT;
+
+    let mut elements = vec![
+        format!(
+            "<text x=\"0\" y=\"20\" font-size=\"16\" font-weight=\"bold\">{year} Benchmarks</text>"
+        ),
+        format!(
+            "<text x=\"{SVG_WIDTH}\" y=\"20\" text-anchor=\"end\">Total: {:.2}ms</text>",
+            timings.total_millis()
+        ),
+    ];
+
+    // legend
+    let mut x = 0.0;
+    for (part, name) in &stages {
+        elements.push(format!(
+            "<rect x=\"{x:.1}\" y=\"34\" width=\"12\" height=\"12\" fill=\"{}\"/>",
+            stage_color(*part)
+        ));
+        elements.push(format!(
+            "<text x=\"{:.1}\" y=\"44\">{name}</text>",
+            x + 16.0
+        ));
+        x += 80.0;
+    }
+
+    // one grid line per power of ten, behind the bars
+    for exponent in bounds.0..=bounds.1 {
+        let nanos = 10_f64.powi(exponent);
+        let x = SVG_LABEL_WIDTH + log_position(nanos, bounds) * chart_width;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let label = format!("{:?}", Duration::from_nanos(nanos as u64));
+
+        elements.push(format!(
+            "<line x1=\"{x:.1}\" y1=\"{SVG_HEADER_HEIGHT}\" x2=\"{x:.1}\" y2=\"{:.1}\" stroke=\"#ddd\"/>",
+            SVG_HEADER_HEIGHT + chart_height
+        ));
+        elements.push(format!(
+            "<text x=\"{x:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#666\">{}</text>",
+            height - 8.0,
+            escape(&label)
+        ));
+    }
+
+    let mut y = SVG_HEADER_HEIGHT;
+    for timing in &timings.data {
+        elements.push(format!(
+            "<text x=\"0\" y=\"{:.1}\" dominant-baseline=\"middle\">Day {}</text>",
+            y + (day_height - SVG_DAY_GAP) / 2.0,
+            timing.day.into_inner()
+        ));
+
+        for (part, name) in &stages {
+            if let (Some(nanos), Some(label)) =
+                (timing.mean_nanos(*part), mean_label(timing, *part))
+            {
+                // bars at the lower bound still get a sliver, so that they do not look missing.
+                let width = (log_position(nanos, bounds) * chart_width).max(1.0);
+                let label = escape(&label);
+
+                elements.push(format!(
+                    "<rect x=\"{SVG_LABEL_WIDTH}\" y=\"{y:.1}\" width=\"{width:.1}\" height=\"{SVG_BAR_HEIGHT}\" fill=\"{}\"><title>Day {} {name}: {label}</title></rect>",
+                    stage_color(*part),
+                    timing.day.into_inner()
+                ));
+                elements.push(format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" dominant-baseline=\"middle\" font-size=\"11\">{label}</text>",
+                    SVG_LABEL_WIDTH + width + 4.0,
+                    y + SVG_BAR_HEIGHT / 2.0
+                ));
+            }
+            y += SVG_BAR_HEIGHT + SVG_BAR_GAP;
+        }
+
+        y += SVG_DAY_GAP;
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{SVG_WIDTH}\" height=\"{height:.0}\" viewBox=\"0 0 {SVG_WIDTH} {height:.0}\" font-family=\"system-ui, sans-serif\" font-size=\"12\">\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n{}\n</svg>\n",
+        elements.join("\n")
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{log_bounds, log_position, render, ReportFormat};
+    use crate::template::readme_benchmarks::TableStyle;
+    use crate::template::stats::MemoryStats;
+    use crate::template::timings::{Timing, Timings};
+    use crate::{day, year};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10µs".into()),
+                    part_2: Some("2.5ms".into()),
+                    total_nanos: 2_510_000.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: Some(MemoryStats {
+                        peak_bytes: 2048,
+                        total_allocations: 3,
+                        total_bytes: 4096,
+                    }),
+                    part_2_memory: None,
+                    parse_memory: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("300ns".into()),
+                    part_2: None,
+                    total_nanos: 300.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("svg".parse::<ReportFormat>().unwrap(), ReportFormat::Svg);
+        assert_eq!("pdf".parse::<ReportFormat>().is_err(), true);
+        assert_eq!(ReportFormat::Html.to_string(), "html");
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(
+            ReportFormat::Csv,
+            year!(2023),
+            &get_mock_timings(),
+            TableStyle::Mean,
+        );
+
+        assert_eq!(
+            csv,
+            "day,stage,mean_ns,median_ns,std_dev_ns,min_ns,max_ns,peak_bytes\n\
+            1,part_1,10000,,,,,2048\n\
+            1,part_2,2500000,,,,,\n\
+            2,part_1,300,,,,,\n"
+        );
+    }
+
+    #[test]
+    fn renders_sortable_html() {
+        let html = render(
+            ReportFormat::Html,
+            year!(2023),
+            &get_mock_timings(),
+            TableStyle::Mean,
+        );
+
+        assert_eq!(
+            html.contains("<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Part 1 memory</th><th>Part 2 memory</th></tr>"),
+            true
+        );
+        assert_eq!(
+            html.contains("<tr><td data-value=\"2\">Day 2</td><td data-value=\"300\">300ns</td><td>-</td><td>-</td><td>-</td></tr>"),
+            true
+        );
+        assert_eq!(html.contains("<td data-value=\"2048\">2.0 KiB</td>"), true);
+        assert_eq!(html.contains("addEventListener(\"click\""), true);
+    }
+
+    #[test]
+    fn renders_svg_chart() {
+        let svg = render(
+            ReportFormat::Svg,
+            year!(2023),
+            &get_mock_timings(),
+            TableStyle::Mean,
+        );
+
+        assert_eq!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""),
+            true
+        );
+        // two legend entries, the background and three bars.
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert_eq!(svg.contains("<title>Day 1 Part 2: 2.5ms</title>"), true);
+        // the axis spans 100ns to 10ms.
+        assert_eq!(svg.contains(">100ns</text>"), true);
+        assert_eq!(svg.contains(">10ms</text>"), true);
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        let bounds = log_bounds([300.0, 10_000.0, 2_500_000.0].into_iter());
+
+        assert_eq!(bounds, (2, 7));
+        assert_eq!(log_position(100.0, bounds), 0.0);
+        assert_eq!(log_position(10_000.0, bounds), 0.4);
+        assert_eq!(log_position(10_000_000.0, bounds), 1.0);
+        assert_eq!(log_bounds([].into_iter()), (0, 1));
+        assert_eq!(log_bounds([1000.0].into_iter()), (3, 4));
+    }
+}
@@ -1,21 +1,35 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
-
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use std::collections::{BTreeMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::{fmt::Display, io, thread};
+
+use crate::template::output::OutputFormat;
+use crate::template::runner::{print_part_result, PartResult, RunOptions};
+use crate::template::{find_solution, Day, Solution, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+use super::{all_days_in, timings::Timings};
+
+/// Run the solutions for a set of days of a year and report their results in the configured format.
+/// Days that are present in `solutions` are run in-process, all other days are run by invoking their binary.
+///
+/// With `jobs` > 1, up to `jobs` days run concurrently. Their results are still printed grouped by day and in day order,
+/// but only once a day is done, rather than part by part.
+///
+/// In JSON mode, nothing is printed and the caller is expected to print all results as a single document.
+pub fn run_multi(
+    solutions: &[Solution],
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    options: RunOptions,
+    jobs: usize,
+) -> Vec<PartResult> {
+    let is_text = options.format == OutputFormat::Text;
+    let mut results: Vec<PartResult> = Vec::with_capacity(days_to_run.len() * 2);
 
     let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
+    let mut print_header = |day: Day| {
+        if is_text {
             if need_space {
                 println!();
             }
@@ -23,27 +37,162 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
+        }
+    };
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days_in(year)
+        .filter(|day| days_to_run.contains(day))
+        .collect();
+
+    if jobs <= 1 {
+        for day in days {
+            print_header(day);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let day_results =
+                run_day(solutions, year, day, is_release, options).unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    vec![]
+                });
 
-            if output.is_empty() {
+            if day_results.is_empty() && is_text {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
-        });
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+            results.extend(day_results);
+        }
+    } else {
+        // parts print nothing in JSON mode, which allows printing them once their day is done.
+        let quiet_options = RunOptions {
+            format: OutputFormat::Json,
+            ..options
+        };
+
+        run_parallel(
+            &days,
+            jobs,
+            |day| run_day(solutions, year, *day, is_release, quiet_options),
+            |day, day_results| {
+                print_header(*day);
+
+                // errors are reported here as well, to keep them next to the output of their day.
+                let day_results = day_results.unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    vec![]
+                });
+
+                for result in &day_results {
+                    print_part_result(result, options.format);
+                }
+
+                if day_results.is_empty() && is_text {
+                    println!("Not solved.");
+                }
+
+                results.extend(day_results);
+            },
+        );
+    }
+
+    if is_text && options.is_timed {
+        let total_millis = Timings::from_results(&results).total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    if is_text && !results.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}Summary:{ANSI_RESET} {}.",
+            format_summary(&results)
+        );
+    }
+
+    results
+}
+
+/// Count the parts per status, e.g. `40 solved, 1 unsolved, 1 panicked`. Statuses without parts are left out.
+pub fn format_summary(results: &[PartResult]) -> String {
+    ["solved", "unsolved", "panicked", "timed_out", "failed"]
+        .iter()
+        .filter_map(|status| {
+            let count = results.iter().filter(|r| r.status() == *status).count();
+            (count > 0).then(|| format!("{count} {}", status.replace('_', " ")))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn run_day(
+    solutions: &[Solution],
+    year: Year,
+    day: Day,
+    is_release: bool,
+    options: RunOptions,
+) -> Result<Vec<PartResult>, String> {
+    match find_solution(solutions, year, day) {
+        Some(solution) => solution.run(options).map_err(|e| e.to_string()),
+        None => child_commands::run_solution(year, day, options, is_release)
+            .map_err(|e| format!("Failed to run solution: {e}")),
+    }
+}
+
+/// Run `task` for every item on up to `jobs` threads. Outputs are passed to `report` in the order of `items`,
+/// each one as soon as the outputs of all previous items have been reported.
+fn run_parallel<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    task: impl Fn(&T) -> R + Sync,
+    mut report: impl FnMut(&T, R),
+) {
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next_index, task) = (&next_index, &task);
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if tx.send((index, task(item))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // the channel closes once every worker is done.
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_report = 0;
+
+        for (index, output) in rx {
+            pending.insert(index, output);
+            while let Some(output) = pending.remove(&next_report) {
+                report(&items[next_report], output);
+                next_report += 1;
+            }
+        }
+    });
+}
+
+/// Print a header for a year when running several years in a row. Only applies to text output.
+pub fn print_year_header(year: Year, is_first: bool, format: OutputFormat) {
+    if format != OutputFormat::Text {
+        return;
+    }
+
+    if !is_first {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Advent of Code {year}{ANSI_RESET}");
+    println!("==================");
+    println!();
 }
 
 #[derive(Debug)]
@@ -58,48 +207,115 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "broken pipe to child process."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", year.bin_name(day))
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their results.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::output::{parse_ndjson, OutputFormat};
+    use crate::template::runner::{print_part_result, Failure, PartResult, RunOptions};
+    use crate::template::{Day, Year};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::Instant,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// The bin reports its results as NDJSON, which are re-printed in the requested output format.
+    /// If the bin exits with an error before a part failed on its own, e.g. because it could not be compiled,
+    /// this is reported as a failed part.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        options: RunOptions,
+        is_release: bool,
+    ) -> Result<Vec<PartResult>, Error> {
+        run_solution_with_input(year, day, options, is_release, None)
+    }
+
+    /// Same as [`run_solution`], but runs the solution against an input file instead of the day's puzzle input.
+    pub fn run_solution_with_input(
+        year: Year,
+        day: Day,
+        options: RunOptions,
+        is_release: bool,
+        input: Option<&Path>,
+    ) -> Result<Vec<PartResult>, Error> {
+        let profile: &[&str] = if is_release { &["--release"] } else { &[] };
+        let input = input.map(|x| x.to_string_lossy());
+        let bin_args = match &input {
+            Some(input) => vec!["--input", input],
+            None => vec![],
+        };
+
+        run_bin(year, day, options, profile, &bin_args)
+    }
+
+    /// Run the solution bin for a given day once with the `dhat-heap` feature, which reports the heap usage of each part.
+    /// The heap profiles of the parts are not written to disk.
+    pub fn profile_memory(
+        year: Year,
+        day: Day,
+        options: RunOptions,
+    ) -> Result<Vec<PartResult>, Error> {
+        let profile = ["--profile", "dhat", "--features", "dhat-heap"];
+        run_bin(year, day, options, &profile, &["--no-heap-profile"])
+    }
+
+    fn run_bin(
+        year: Year,
+        day: Day,
+        options: RunOptions,
+        profile: &[&str],
+        bin_args: &[&str],
+    ) -> Result<Vec<PartResult>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+        let bin_name = year.bin_name(day);
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+        args.extend(profile);
+        args.extend(["--", "--format", "ndjson"]);
 
-        if is_timed {
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        // mirror `--timeout` flag to child invocations, which enforce it themselves.
+        let timeout = options.timeout.map(|x| x.as_secs_f64().to_string());
+        if let Some(timeout) = &timeout {
+            args.extend(["--timeout", timeout]);
+        }
+
+        args.extend(bin_args);
+
+        let timer = Instant::now();
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while collecting result records from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env_remove("CARGO_MANIFEST_DIR")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -107,150 +323,107 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut results = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
-
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
-    }
-
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        for line in stdout.split(b'\n') {
+            let line = String::from_utf8_lossy(&line?).trim_end().to_string();
+            match parse_ndjson(&line) {
+                Some(result) => {
+                    print_part_result(&result, options.format);
+                    results.push(result);
                 }
+                // forward anything else the solution printed, without breaking machine-readable output.
+                None if options.format == OutputFormat::Text => println!("{line}"),
+                None => eprintln!("{line}"),
+            }
+        }
 
-                timings.total_nanos += nanos;
-            });
+        thread.join().map_err(|_| Error::BrokenPipe)?;
+        let status = cmd.wait()?;
+
+        if !status.success() && results.iter().all(|result| result.failure.is_none()) {
+            let message = match status.code() {
+                Some(code) => format!("exited with code {code}"),
+                None => "terminated by a signal".into(),
+            };
+            let part = results.last().map_or(1, |result| result.part + 1);
+            let result =
+                PartResult::failed(year, day, part, Failure::Failed(message), timer.elapsed());
+            print_part_result(&result, options.format);
+            results.push(result);
+        }
 
-        timings
+        Ok(results)
     }
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{format_summary, run_parallel};
+    use crate::template::runner::{Failure, PartResult};
+    use crate::{day, year};
+
+    #[test]
+    fn reports_parallel_outputs_in_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let started = Mutex::new(vec![]);
+        let mut reported = vec![];
+
+        run_parallel(
+            &items,
+            4,
+            |x| {
+                started.lock().unwrap().push(*x);
+                // finish later items first.
+                thread::sleep(Duration::from_millis(20 - x));
+                x * 2
+            },
+            |x, output| reported.push((*x, output)),
+        );
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        assert_eq!(
+            reported,
+            items.iter().map(|x| (*x, x * 2)).collect::<Vec<_>>()
+        );
+        assert_eq!(started.lock().unwrap().len(), 20);
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn handles_more_jobs_than_items() {
+        let mut reported = vec![];
+        run_parallel(&[1, 2], 8, |x| x + 1, |_, output| reported.push(output));
+        assert_eq!(reported, vec![2, 3]);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn summarizes_statuses() {
+        let result = |answer: Option<&str>, failure| PartResult {
+            answer: answer.map(Into::into),
+            failure,
+            ..PartResult::failed(year!(2023), day!(1), 1, Failure::TimedOut, Duration::ZERO)
+        };
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let results = [
+            result(Some("1"), None),
+            result(Some("2"), None),
+            result(None, None),
+            result(None, Some(Failure::Panicked("oops".into()))),
+            result(None, Some(Failure::TimedOut)),
+        ];
+
+        assert_eq!(
+            format_summary(&results),
+            "2 solved, 1 unsolved, 1 panicked, 1 timed out"
+        );
     }
 }
@@ -1,70 +1,685 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
+use crate::template::answers::{get_answers_dir, Answers, Verdict};
+use crate::template::input::read_custom_input;
+use crate::template::output::{self, OutputFormat};
+use crate::template::params::{with_input_kind, InputKind};
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::submission::{self, SubmitOutcome};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{backend, try_read_year_file, Day, Solution, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The number of the shared parse stage of a solution, see [`solution!`](crate::solution).
+/// It is reported like a part, without an answer.
+pub const PARSE_STAGE: u8 = 0;
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub year: Year,
+    pub day: Day,
+    /// The part, or [`PARSE_STAGE`] for the result of parsing the input.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+    /// Statistics of the benchmark samples, if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// Heap usage of the first run, if it ran with the `dhat-heap` feature.
+    pub memory: Option<MemoryStats>,
+    /// Whether the answer matches the known correct answer in the `answers` folder of the year.
+    pub verdict: Verdict,
+    /// Why the part did not run to completion, if it did not.
+    pub failure: Option<Failure>,
+}
+
+impl PartResult {
+    /// The result of a part that did not run to completion.
+    pub fn failed(year: Year, day: Day, part: u8, failure: Failure, duration: Duration) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            answer: None,
+            duration,
+            samples: 1,
+            stats: None,
+            memory: None,
+            verdict: Answers::read(year, day).verify(part, None),
+            failure: Some(failure),
+        }
+    }
+
+    pub fn is_parse_stage(&self) -> bool {
+        self.part == PARSE_STAGE
+    }
+
+    /// Short, machine-readable description of the outcome.
+    pub fn status(&self) -> &'static str {
+        match &self.failure {
+            Some(failure) => failure.status(),
+            None if self.is_parse_stage() => "parsed",
+            None if self.answer.is_some() => "solved",
+            None => "unsolved",
+        }
+    }
+}
+
+/// Reasons for a part to not run to completion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part panicked. Contains the panic message.
+    Panicked(String),
+    /// The part did not finish within the timeout.
+    TimedOut,
+    /// The part returned an error, or the solution binary exited with an error before reporting the part.
+    /// Contains the error with its sources, or a description of the exit status.
+    Failed(String),
+}
+
+impl Failure {
+    pub fn status(&self) -> &'static str {
+        match self {
+            Failure::Panicked(_) => "panicked",
+            Failure::TimedOut => "timed_out",
+            Failure::Failed(_) => "failed",
+        }
+    }
+
+    /// Details about the failure, if any.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Failure::Panicked(message) | Failure::Failed(message) => Some(message),
+            Failure::TimedOut => None,
+        }
+    }
+}
+
+/// The return value of a solution part. Parts return either an [`Option`], where [`None`] means
+/// that the part is not solved yet, or a [`Result`], where an error means that the part failed.
+pub trait PartOutput {
+    /// The answer, if the part returned one.
+    fn answer(&self) -> Option<&dyn Display>;
+
+    /// Convert into the answer, or into a description of the error.
+    fn into_answer(self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn answer(&self) -> Option<&dyn Display> {
+        self.as_ref().map(|x| x as &dyn Display)
+    }
+
+    fn into_answer(self) -> Result<Option<String>, String> {
+        Ok(self.map(|x| x.to_string()))
+    }
+}
+
+/// Errors are described by their [`Display`] implementation. The [`solution!`](crate::solution) macro
+/// turns errors that convert into a `Box<dyn Error>` into an [`ErrorChain`] first, so that their sources are included.
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn answer(&self) -> Option<&dyn Display> {
+        self.as_ref().ok().map(|x| x as &dyn Display)
+    }
+
+    fn into_answer(self) -> Result<Option<String>, String> {
+        self.map(|x| Some(x.to_string())).map_err(|e| e.to_string())
+    }
+}
+
+/// An error of a part, rendered together with its sources, see [`format_error_chain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorChain(String);
+
+impl ErrorChain {
+    pub fn new(error: &dyn Error) -> Self {
+        Self(format_error_chain(error))
+    }
+}
+
+impl Display for ErrorChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Not part of the public API
+//
+// Picks how the output of a part is reported without specialisation, by method resolution:
+// `(&output).__output_kind()` resolves to [`Chained`](__output_kind::Chained) for results whose error
+// converts into a `Box<dyn Error>`, and falls back to [`Plain`](__output_kind::Plain) for every other output.
+#[doc(hidden)]
+pub mod __output_kind {
+    use std::error::Error;
+
+    use super::ErrorChain;
+
+    pub struct Chained;
+    pub struct Plain;
+
+    pub trait ChainedKind {
+        fn __output_kind(&self) -> Chained {
+            Chained
+        }
+    }
+
+    impl<T, E: Into<Box<dyn Error>>> ChainedKind for Result<T, E> {}
+
+    pub trait PlainKind {
+        fn __output_kind(&self) -> Plain {
+            Plain
+        }
+    }
+
+    impl<R> PlainKind for &R {}
+
+    impl Chained {
+        pub fn convert<T, E: Into<Box<dyn Error>>>(
+            self,
+            output: Result<T, E>,
+        ) -> Result<T, ErrorChain> {
+            output.map_err(|e| ErrorChain::new(e.into().as_ref()))
+        }
+    }
+
+    impl Plain {
+        pub fn convert<R>(self, output: R) -> R {
+            output
+        }
+    }
+}
+
+/// Render an error together with its sources, e.g. `invalid input: invalid digit found in string`.
+pub fn format_error_chain(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    while let Some(e) = source {
+        message.push_str(&format!(": {e}"));
+        source = e.source();
+    }
+
+    message
+}
+
+/// Options that control how solution parts are run and reported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_timed: bool,
+    pub format: OutputFormat,
+    /// Give up on a part if it does not finish within this duration, including benching.
+    pub timeout: Option<Duration>,
+    /// The kind of input that parts run against, which selects the values of their [`Param`](crate::template::Param)s.
+    pub input: InputKind,
+    /// Parts run against an input passed with `--input`, so their answers can not be verified or submitted.
+    pub is_custom_input: bool,
+    /// Write the heap profile of each part to `dhat-heap-<day>-<part>.json` when running with the `dhat-heap` feature.
+    /// Memory is reported either way.
+    pub save_heap_profile: bool,
+}
+
+impl RunOptions {
+    /// Parse the options that are mirrored to solution binaries from the command-line.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let format = match args.iter().position(|x| x == "--format") {
+            Some(index) => match args.get(index + 1).map(|x| x.parse::<OutputFormat>()) {
+                Some(Ok(format)) => format,
+                Some(Err(e)) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+                None => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+                    process::exit(1);
+                }
+            },
+            None => OutputFormat::default(),
+        };
+
+        let timeout = match args.iter().position(|x| x == "--timeout") {
+            Some(index) => match args.get(index + 1).map(|x| parse_timeout(x)) {
+                Some(Ok(timeout)) => Some(timeout),
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 10");
+                    process::exit(1);
+                }
+            },
+            None => None,
+        };
+
+        Self {
+            is_timed: args.iter().any(|x| x == "--time"),
+            format,
+            timeout,
+            input: InputKind::Real,
+            is_custom_input: args.iter().any(|x| x == "--input"),
+            save_heap_profile: !args.iter().any(|x| x == "--no-heap-profile"),
+        }
+    }
+}
+
+/// Read the puzzle input of a day for a solution binary, or the input passed with `--input <path>` (`-` for stdin).
+/// Exits with the reason if the input can not be used.
+pub fn read_puzzle_input(year: Year, day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let input = match args.iter().position(|x| x == "--input") {
+        Some(index) => match args.get(index + 1) {
+            Some(path) => read_custom_input(path),
+            None => {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --input path/to/input.txt");
+                process::exit(1);
+            }
+        },
+        None => try_read_year_file("inputs", year, day),
+    };
+
+    input.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Parse a timeout in seconds, e.g. `10` or `0.5`.
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("expecting a timeout in seconds, e.g. `10`, got `{s}`"))
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    options: RunOptions,
+) -> PartResult {
+    let watchdog = options
+        .timeout
+        .map(|timeout| start_watchdog(year, day, part, options.format, timeout));
+
+    let result = execute_part(func, input, year, day, part, options);
+    drop(watchdog);
+
+    if let Some(answer) = result.answer.as_ref().filter(|_| !options.is_custom_input) {
+        submit_result(answer, year, day, part);
+    }
+
+    result
+}
+
+/// Run a solution part and report its result, returning the outcome as a typed value.
+///
+/// In text mode, the result is printed as it becomes available. In NDJSON mode, one record is
+/// printed once the part is done. In JSON mode, nothing is printed and the caller is expected
+/// to print all results as a single document.
+pub fn execute_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    options: RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
+    let heap_profile = heap_profile_path(day, part);
+    let is_text = options.format == OutputFormat::Text;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let timer = Instant::now();
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        with_input_kind(options.input, || {
+            run_timed(func, input, &heap_profile, options, |result| {
+                if is_text {
+                    print_result(&result.answer(), &part_str, "");
+                    if options.is_timed {
+                        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                        let _ = stdout().flush();
+                    }
+                }
+            })
+        })
+    }));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let result = match outcome {
+        Ok((result, duration, samples, stats, memory)) => match result.into_answer() {
+            Ok(answer) => {
+                let verdict = if options.is_custom_input {
+                    Verdict::Unknown
+                } else {
+                    Answers::read(year, day).verify(part, answer.as_deref())
+                };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+                PartResult {
+                    year,
+                    day,
+                    part,
+                    answer,
+                    duration,
+                    samples,
+                    stats,
+                    memory,
+                    verdict,
+                    failure: None,
+                }
+            }
+            Err(message) => PartResult::failed(year, day, part, Failure::Failed(message), duration),
+        },
+        Err(payload) => PartResult::failed(
+            year,
+            day,
+            part,
+            Failure::Panicked(panic_message(payload.as_ref())),
+            timer.elapsed(),
+        ),
+    };
+
+    print_part_result(&result, options.format);
+
+    result
+}
+
+/// Parse the input of a solution with a shared parse stage, and report the time it took as [`PARSE_STAGE`].
+/// Returns the parsed input if parsing did not fail, see [`run_part`] for timeouts.
+pub fn run_parse<'a, D>(
+    parse: impl Fn(&'a str) -> D,
+    input: &'a str,
+    year: Year,
+    day: Day,
+    options: RunOptions,
+) -> (Option<D>, PartResult) {
+    let watchdog = options
+        .timeout
+        .map(|timeout| start_watchdog(year, day, PARSE_STAGE, options.format, timeout));
+
+    let outcome = execute_parse(parse, input, year, day, options);
+    drop(watchdog);
+
+    outcome
+}
+
+/// Parse the input of a solution and report it like [`execute_part`] does, timing parsing on its own.
+pub fn execute_parse<'a, D>(
+    parse: impl Fn(&'a str) -> D,
+    input: &'a str,
+    year: Year,
+    day: Day,
+    options: RunOptions,
+) -> (Option<D>, PartResult) {
+    let heap_profile = heap_profile_path(day, PARSE_STAGE);
+    let is_text = options.format == OutputFormat::Text;
+
+    let timer = Instant::now();
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        with_input_kind(options.input, || {
+            run_timed(&parse, input, &heap_profile, options, |_| {
+                if is_text && options.is_timed {
+                    print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+                    let _ = stdout().flush();
+                }
+            })
+        })
+    }));
+
+    let (data, result) = match outcome {
+        Ok((data, duration, samples, stats, memory)) => (
+            Some(data),
+            PartResult {
+                year,
+                day,
+                part: PARSE_STAGE,
+                answer: None,
+                duration,
+                samples,
+                stats,
+                memory,
+                verdict: Verdict::Unknown,
+                failure: None,
+            },
+        ),
+        Err(payload) => (
+            None,
+            PartResult::failed(
+                year,
+                day,
+                PARSE_STAGE,
+                Failure::Panicked(panic_message(payload.as_ref())),
+                timer.elapsed(),
+            ),
+        ),
+    };
+
+    print_part_result(&result, options.format);
+
+    (data, result)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|x| (*x).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into())
+}
+
+/// Start a thread that reports a part as timed out and exits the process if the part does not finish in time.
+/// The watchdog is stopped by dropping the returned sender.
+fn start_watchdog(
+    year: Year,
+    day: Day,
+    part: u8,
+    format: OutputFormat,
+    timeout: Duration,
+) -> Sender<()> {
+    let (tx, rx) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        if rx.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+            let result = PartResult::failed(year, day, part, Failure::TimedOut, timeout);
+            print_part_result(&result, format);
+            if format == OutputFormat::Json {
+                output::print_json(&[result]);
+            }
+            process::exit(1);
+        }
+    });
+
+    tx
+}
+
+/// Run a type-erased solution on its own thread, and give up on a stage if it does not finish within `timeout`.
+/// A stage that timed out keeps running in the background until the process exits. The parts after it are
+/// run on a new thread, which parses the input again for solutions with a shared parse stage.
+pub fn run_with_timeout(
+    solution: &Solution,
+    input: &Arc<str>,
+    parts: &[u8],
+    options: RunOptions,
+    timeout: Duration,
+) -> Vec<PartResult> {
+    let (year, day) = (solution.year, solution.day);
+
+    // the solution prints nothing in JSON mode, so it can not print over later output once abandoned.
+    let quiet_options = RunOptions {
+        format: OutputFormat::Json,
+        ..options
+    };
+
+    let mut pending: Vec<u8> = solution
+        .parts
+        .iter()
+        .copied()
+        .filter(|part| *part != PARSE_STAGE && parts.contains(part))
+        .collect();
+    let mut results: Vec<PartResult> = vec![];
+
+    while !pending.is_empty() {
+        let (tx, rx) = mpsc::channel();
+        let (run, input, thread_parts) = (solution.run, Arc::clone(input), pending.clone());
+        thread::spawn(move || {
+            run(&input, &thread_parts, quiet_options, &mut |result| {
+                let _ = tx.send(result);
+            });
+        });
+
+        let mut is_parsing = solution.has_parse_stage();
+        loop {
+            let stage = if is_parsing {
+                PARSE_STAGE
+            } else if let Some(part) = pending.first() {
+                *part
+            } else {
+                break;
+            };
+
+            // a thread that timed out is stuck, and one that disconnected is gone. Either way, it is abandoned.
+            let (result, is_abandoned) = match rx.recv_timeout(timeout) {
+                Ok(result) => (result, false),
+                Err(RecvTimeoutError::Timeout) => (
+                    PartResult::failed(year, day, stage, Failure::TimedOut, timeout),
+                    true,
+                ),
+                Err(RecvTimeoutError::Disconnected) => (
+                    PartResult::failed(
+                        year,
+                        day,
+                        stage,
+                        Failure::Failed("the part stopped without a result.".into()),
+                        Duration::ZERO,
+                    ),
+                    true,
+                ),
+            };
+
+            if result.is_parse_stage() {
+                is_parsing = false;
+                // no part can run if parsing failed.
+                if result.failure.is_some() {
+                    pending.clear();
+                }
+            } else {
+                pending.retain(|part| *part != result.part);
+            }
+
+            // a restarted thread parses again, its parse stage is only reported once.
+            if !(result.is_parse_stage() && results.iter().any(PartResult::is_parse_stage)) {
+                print_part_result(&result, options.format);
+                results.push(result);
+            }
+
+            if is_abandoned {
+                break;
+            }
+        }
+    }
+
+    results
+}
+
+/// Print the final result of a part in the given format.
+/// This is a no-op for [`OutputFormat::Json`], which is printed as a whole.
+pub fn print_part_result(result: &PartResult, format: OutputFormat) {
+    match format {
+        OutputFormat::Text if result.failure.is_some() => print_failure(result),
+        OutputFormat::Text if result.is_parse_stage() => {
+            print!("\r");
+            println!(
+                "Parse:{}{}",
+                format_duration(&result.duration, result.samples, result.stats.as_ref()),
+                format_memory(result.memory.as_ref())
+            );
+        }
+        OutputFormat::Text => print_result(
+            &result.answer,
+            &format!("Part {}", result.part),
+            &format!(
+                " {}{}{}",
+                result.verdict.symbol(),
+                format_duration(&result.duration, result.samples, result.stats.as_ref()),
+                format_memory(result.memory.as_ref())
+            ),
+        ),
+        OutputFormat::Ndjson => output::print_ndjson(result),
+        OutputFormat::Json => {}
     }
 }
 
+/// The file that the heap profile of a part is written to, e.g. `dhat-heap-01-2.json`.
+/// Each part of a day gets its own file, so that they do not overwrite each other.
+fn heap_profile_path(day: Day, part: u8) -> String {
+    format!("dhat-heap-{day}-{part}.json")
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `dhat-heap` feature, the heap usage of the first run is recorded, and its profile is written to `heap_profile`.
+#[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))]
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    heap_profile: &str,
+    options: RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<BenchStats>, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        {
+            let _profiler = if options.save_heap_profile {
+                dhat::Profiler::builder().file_name(heap_profile).build()
+            } else {
+                dhat::Profiler::builder().testing().build()
+            };
+            let result = func(input);
+            (result, Some(MemoryStats::from(dhat::HeapStats::get())))
+        }
 
-        func(input)
+        #[cfg(not(feature = "dhat-heap"))]
+        (func(input), None)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if options.is_timed {
+        let (duration, samples, stats) = bench(func, input, &base_time);
+        (result, duration, samples, Some(stats), memory)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, 1, None, memory)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench a function after warming it up. Warm-up runs take approx. 0.1 seconds and are discarded.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, BenchStats) {
+    let base_nanos = cmp::max(base_time.as_nanos(), 10);
+    let warmup_iterations = (Duration::from_millis(100).as_nanos() / base_nanos).clamp(1, 1000);
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / base_nanos).clamp(10, 10000);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,29 +691,51 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: `timers` is never empty, we always run at least 10 iterations.
+    let stats = BenchStats::from_samples(&timers, warmup_iterations).unwrap_or_default();
+
+    (stats.mean, bench_iterations, stats)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, samples: u128, stats: Option<&BenchStats>) -> String {
+    match stats {
+        _ if samples == 1 => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {samples} samples, median {:.1?} ± {:.1?})",
+            stats.median, stats.std_dev
+        ),
+        None => format!(" ({duration:.1?} @ {samples} samples)"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_memory(memory: Option<&MemoryStats>) -> String {
+    match memory {
+        Some(memory) => format!(" [{}]", memory.summary()),
+        None => String::new(),
     }
 }
 
+fn print_failure(result: &PartResult) {
+    let Some(failure) = &result.failure else {
+        return;
+    };
+
+    let description = match failure {
+        Failure::Panicked(message) => format!("panicked: {message}"),
+        Failure::TimedOut => format!("timed out after {:.1?}", result.duration),
+        Failure::Failed(message) => format!("failed: {message}"),
+    };
+
+    let label = if result.is_parse_stage() {
+        "Parse".into()
+    } else {
+        format!("Part {}", result.part)
+    };
+
+    print!("\r");
+    println!("{label}: ✖ {ANSI_ITALIC}{description}{ANSI_RESET}");
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -136,12 +773,11 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a backend is available, see [`backend::select`].
+///  3. the answer is not known to be wrong, or already accepted.
+///
+/// Accepted answers are stored as known correct answers, rejected answers are logged as wrong guesses.
+fn submit_result(result: &str, year: Year, day: Day, part: u8) -> Option<SubmitOutcome> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -164,11 +800,102 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answers_dir = get_answers_dir(year);
+
+    if let Err(e) = submission::check_answer(&answers_dir, day, part, result) {
+        eprintln!("Not submitting {result} for part {part}: {e}");
+        return None;
+    }
+
+    let backend = match backend::select() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result via {}...", backend.name());
+
+    match submission::submit_answer(backend.as_ref(), &answers_dir, year, day, part, result) {
+        Ok((outcome, response)) => {
+            println!("{}", response.trim_end());
+            println!("Submission was {ANSI_BOLD}{outcome}{ANSI_RESET}.");
+            Some(outcome)
+        }
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            None
+        }
     }
+}
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fmt::Display;
+    use std::num::ParseIntError;
+
+    use super::__output_kind::{ChainedKind, PlainKind};
+    use super::PartOutput;
+
+    #[derive(Debug)]
+    struct InvalidInput(ParseIntError);
+
+    /// An error that only implements [`Display`].
+    struct NotANumber;
+
+    impl Display for NotANumber {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("not a number")
+        }
+    }
+
+    impl Display for InvalidInput {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("invalid input")
+        }
+    }
+
+    impl std::error::Error for InvalidInput {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(42).into_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.into_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(42).into_answer(), Ok(Some("42".into())));
+        assert_eq!(
+            Err::<u32, _>("not implemented").into_answer(),
+            Err("not implemented".into())
+        );
+    }
+
+    #[test]
+    fn renders_error_sources() {
+        let output = Err::<u32, _>(InvalidInput("abc".parse::<u32>().unwrap_err()));
+        assert_eq!(
+            (&output).__output_kind().convert(output).into_answer(),
+            Err("invalid input: invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn renders_display_errors() {
+        let output = Err::<u32, _>(NotANumber);
+        assert_eq!(
+            (&output).__output_kind().convert(output).into_answer(),
+            Err("not a number".into())
+        );
+
+        let output = Some(42);
+        assert_eq!(
+            (&output).__output_kind().convert(output).into_answer(),
+            Ok(Some("42".into()))
+        );
+    }
 }
@@ -0,0 +1,348 @@
+/// Summary statistics of benchmark samples.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+/// Statistics of the samples collected while benching a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    /// Number of iterations that ran before measuring, and were discarded.
+    pub warmup: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub p5: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub outliers: Outliers,
+}
+
+/// Number of samples outside of the Tukey fences.
+/// Mild outliers lie more than 1.5 IQR outside of the interquartile range, severe outliers more than 3 IQR.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+impl BenchStats {
+    /// Compute statistics for a set of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration], warmup: u128) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+
+        let mut outliers = Outliers::default();
+        for &x in &nanos {
+            if x < q1 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if x < q1 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if x > q3 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if x > q3 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+
+        Some(Self {
+            warmup,
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&nanos, 50.0)),
+            p5: from_nanos(percentile(&nanos, 5.0)),
+            p95: from_nanos(percentile(&nanos, 95.0)),
+            std_dev: from_nanos(variance.sqrt()),
+            min: from_nanos(nanos[0]),
+            max: from_nanos(nanos[nanos.len() - 1]),
+            outliers,
+        })
+    }
+}
+
+/// Heap usage of a solution part, as recorded by dhat with the `dhat-heap` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Most bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    pub total_allocations: u64,
+    pub total_bytes: u64,
+}
+
+impl MemoryStats {
+    /// Short summary, e.g. `peak 1.2 KiB, 35 allocations, 4.5 KiB total`.
+    pub fn summary(&self) -> String {
+        format!(
+            "peak {}, {} allocations, {} total",
+            format_bytes(self.peak_bytes),
+            self.total_allocations,
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+impl From<dhat::HeapStats> for MemoryStats {
+    fn from(value: dhat::HeapStats) -> Self {
+        Self {
+            peak_bytes: value.max_bytes as u64,
+            total_allocations: value.total_blocks,
+            total_bytes: value.total_bytes,
+        }
+    }
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.2 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Linearly interpolated percentile of sorted, non-empty values.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = pct / 100.0 * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = |x: Duration| JsonValue::Number(x.as_nanos() as f64);
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = |x: usize| JsonValue::Number(x as f64);
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("p5_nanos".into(), nanos(value.p5));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("max_nanos".into(), nanos(value.max));
+
+        let mut outliers: HashMap<String, JsonValue> = HashMap::new();
+        outliers.insert("low_severe".into(), count(value.outliers.low_severe));
+        outliers.insert("low_mild".into(), count(value.outliers.low_mild));
+        outliers.insert("high_mild".into(), count(value.outliers.high_mild));
+        outliers.insert("high_severe".into(), count(value.outliers.high_severe));
+        map.insert("outliers".into(), JsonValue::Object(outliers));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let outliers = json
+            .get("outliers")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected stats.outliers to be a JSON object.")?;
+
+        let number = |map: &HashMap<String, JsonValue>, key: &str| {
+            map.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let nanos = |key: &str| number(json, key).map(from_nanos);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| number(outliers, key).map(|x| x as usize);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let warmup = number(json, "warmup")? as u128;
+
+        Ok(BenchStats {
+            warmup,
+            mean: nanos("mean_nanos")?,
+            median: nanos("median_nanos")?,
+            p5: nanos("p5_nanos")?,
+            p95: nanos("p95_nanos")?,
+            std_dev: nanos("std_dev_nanos")?,
+            min: nanos("min_nanos")?,
+            max: nanos("max_nanos")?,
+            outliers: Outliers {
+                low_severe: count("low_severe")?,
+                low_mild: count("low_mild")?,
+                high_mild: count("high_mild")?,
+                high_severe: count("high_severe")?,
+            },
+        })
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let bytes = |x: u64| JsonValue::Number(x as f64);
+
+        JsonValue::Object(HashMap::from([
+            ("peak_bytes".into(), bytes(value.peak_bytes)),
+            ("total_allocations".into(), bytes(value.total_allocations)),
+            ("total_bytes".into(), bytes(value.total_bytes)),
+        ]))
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            total_allocations: number("total_allocations")?,
+            total_bytes: number("total_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_bytes, BenchStats, MemoryStats};
+    use tinyjson::JsonValue;
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[], 0), None);
+    }
+
+    #[test]
+    fn computes_summary_statistics() {
+        let stats = BenchStats::from_samples(&samples(&[5, 1, 4, 2, 3]), 10).unwrap();
+        assert_eq!(stats.warmup, 10);
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.max, Duration::from_nanos(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1));
+        assert_eq!(stats.outliers.total(), 0);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let stats =
+            BenchStats::from_samples(&samples(&(0..=100).map(|x| x * 10).collect::<Vec<_>>()), 0)
+                .unwrap();
+        assert_eq!(stats.p5, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(950));
+    }
+
+    #[test]
+    fn classifies_outliers() {
+        let stats =
+            BenchStats::from_samples(&samples(&[10, 10, 11, 11, 12, 12, 13, 13, 17, 100]), 0)
+                .unwrap();
+        assert_eq!(stats.outliers.high_mild, 1);
+        assert_eq!(stats.outliers.high_severe, 1);
+        assert_eq!(stats.outliers.low_mild + stats.outliers.low_severe, 0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&samples(&[10, 10, 11, 12, 100]), 3).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn summarises_and_roundtrips_memory() {
+        let memory = MemoryStats {
+            peak_bytes: 2048,
+            total_allocations: 35,
+            total_bytes: 4608,
+        };
+        assert_eq!(
+            memory.summary(),
+            "peak 2.0 KiB, 35 allocations, 4.5 KiB total"
+        );
+        assert_eq!(
+            MemoryStats::try_from(&JsonValue::from(&memory)).unwrap(),
+            memory
+        );
+    }
+}
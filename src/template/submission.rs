@@ -0,0 +1,353 @@
+/// Module that classifies responses to submitted answers, and keeps a log of wrong guesses.
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::backend::{AocBackend, BackendError};
+use crate::template::history::format_timestamp;
+use crate::template::{Day, Year};
+
+/// The response of the server to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint whether it is too high or too low.
+    Wrong,
+    /// An answer was submitted too recently. Contains the time left to wait, if present, e.g. `4m 23s`.
+    RateLimited(Option<String>),
+    /// The response could not be classified, e.g. because the part was already solved.
+    Unknown,
+}
+
+impl SubmitOutcome {
+    /// Classify the response text of a submission.
+    pub fn classify(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if response.contains("answer is too high") {
+            SubmitOutcome::TooHigh
+        } else if response.contains("answer is too low") {
+            SubmitOutcome::TooLow
+        } else if response.contains("That's not the right answer") {
+            SubmitOutcome::Wrong
+        } else if response.contains("You gave an answer too recently") {
+            let wait = response
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.trim().to_string());
+            SubmitOutcome::RateLimited(wait)
+        } else {
+            SubmitOutcome::Unknown
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Wrong
+        )
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => f.write_str("correct"),
+            SubmitOutcome::TooHigh => f.write_str("too high"),
+            SubmitOutcome::TooLow => f.write_str("too low"),
+            SubmitOutcome::Wrong => f.write_str("wrong"),
+            SubmitOutcome::RateLimited(Some(wait)) => write!(f, "rate-limited ({wait} left)"),
+            SubmitOutcome::RateLimited(None) => f.write_str("rate-limited"),
+            SubmitOutcome::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An answer that was rejected by the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrongGuess {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+}
+
+impl WrongGuess {
+    /// Whether this guess proves that `answer` is wrong as well.
+    /// This is the case for the same answer, or for numbers beyond a guess that was too high or too low.
+    pub fn rules_out(&self, answer: &str) -> bool {
+        if self.answer == answer {
+            return true;
+        }
+
+        let (Ok(guess), Ok(answer)) = (self.answer.parse::<i128>(), answer.parse::<i128>()) else {
+            return false;
+        };
+
+        match self.outcome {
+            SubmitOutcome::TooHigh => answer >= guess,
+            SubmitOutcome::TooLow => answer <= guess,
+            _ => false,
+        }
+    }
+}
+
+/// Append a wrong guess to the log of a day in `dir`.
+pub fn log_wrong_guess(dir: &Path, day: Day, guess: &WrongGuess) -> Result<(), Error> {
+    fs::create_dir_all(dir)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_log_path(dir, day))?;
+
+    writeln!(
+        file,
+        "{}\t{}\t{}\t{}",
+        format_timestamp(timestamp),
+        guess.part,
+        guess.outcome,
+        guess.answer
+    )
+}
+
+/// Read all wrong guesses of a day from the log in `dir`. If the log is not present, returns no guesses.
+pub fn read_wrong_guesses(dir: &Path, day: Day) -> Vec<WrongGuess> {
+    fs::read_to_string(get_log_path(dir, day))
+        .map(|s| s.lines().filter_map(parse_log_line).collect())
+        .unwrap_or_default()
+}
+
+fn parse_log_line(line: &str) -> Option<WrongGuess> {
+    let mut fields = line.splitn(4, '\t').skip(1);
+
+    let part = fields.next()?.parse().ok()?;
+    let outcome = match fields.next()? {
+        "too high" => SubmitOutcome::TooHigh,
+        "too low" => SubmitOutcome::TooLow,
+        _ => SubmitOutcome::Wrong,
+    };
+    let answer = fields.next()?.to_string();
+
+    Some(WrongGuess {
+        part,
+        answer,
+        outcome,
+    })
+}
+
+fn get_log_path(dir: &Path, day: Day) -> PathBuf {
+    dir.join(format!("{day}-wrong.log"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reasons for not submitting an answer.
+#[derive(Debug)]
+pub enum SubmitError {
+    AlreadyAccepted,
+    /// The answer differs from the known correct answer.
+    DiffersFromKnownAnswer,
+    /// A previous wrong guess proves that the answer is wrong.
+    RuledOut(WrongGuess),
+    Backend(BackendError),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::AlreadyAccepted => write!(f, "it was already accepted."),
+            SubmitError::DiffersFromKnownAnswer => {
+                write!(f, "it does not match the known correct answer.")
+            }
+            SubmitError::RuledOut(guess) => write!(
+                f,
+                "it is known to be wrong, a previous guess of {} was {}.",
+                guess.answer, guess.outcome
+            ),
+            SubmitError::Backend(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Check that an answer is not already known to be right or wrong, using the records in `dir`.
+pub fn check_answer(dir: &Path, day: Day, part: u8, answer: &str) -> Result<(), SubmitError> {
+    match Answers::read_in(dir, day).verify(part, Some(answer)) {
+        Verdict::Correct => return Err(SubmitError::AlreadyAccepted),
+        Verdict::Wrong => return Err(SubmitError::DiffersFromKnownAnswer),
+        Verdict::Unknown => {}
+    }
+
+    match read_wrong_guesses(dir, day)
+        .into_iter()
+        .find(|guess| guess.part == part && guess.rules_out(answer))
+    {
+        Some(guess) => Err(SubmitError::RuledOut(guess)),
+        None => Ok(()),
+    }
+}
+
+/// Submit an answer via `backend` if [`check_answer`] passes, returning the outcome and the response text.
+/// Accepted answers are stored as known correct answers in `dir`, rejected answers are logged as wrong guesses.
+pub fn submit_answer(
+    backend: &dyn AocBackend,
+    dir: &Path,
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<(SubmitOutcome, String), SubmitError> {
+    check_answer(dir, day, part, answer)?;
+
+    let response = backend
+        .submit(year, day, part, answer)
+        .map_err(SubmitError::Backend)?;
+    let outcome = SubmitOutcome::classify(&response);
+
+    if outcome == SubmitOutcome::Correct {
+        let mut answers = Answers::read_in(dir, day);
+        answers.set(part, answer);
+        if let Err(e) = answers.store_in(dir, day) {
+            eprintln!("Failed to store correct answer: {e}");
+        }
+    } else if outcome.is_wrong() {
+        let guess = WrongGuess {
+            part,
+            answer: answer.into(),
+            outcome: outcome.clone(),
+        };
+        if let Err(e) = log_wrong_guess(dir, day, &guess) {
+            eprintln!("Failed to log wrong guess: {e}");
+        }
+    }
+
+    Ok((outcome, response))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{
+        check_answer, parse_log_line, submit_answer, SubmitError, SubmitOutcome, WrongGuess,
+    };
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            backend::{BackendCall, RecordingBackend},
+            Year,
+        },
+    };
+
+    #[test]
+    fn classifies_responses() {
+        assert_eq!(
+            SubmitOutcome::classify("That's the right answer! You are one gold star closer."),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            SubmitOutcome::classify("That's not the right answer; your answer is too high."),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            SubmitOutcome::classify("That's not the right answer; your answer is too low."),
+            SubmitOutcome::TooLow
+        );
+        assert_eq!(
+            SubmitOutcome::classify("That's not the right answer. If you're stuck, ..."),
+            SubmitOutcome::Wrong
+        );
+        assert_eq!(
+            SubmitOutcome::classify("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 23s left to wait."),
+            SubmitOutcome::RateLimited(Some("4m 23s".into()))
+        );
+        assert_eq!(
+            SubmitOutcome::classify("You don't seem to be solving the right level."),
+            SubmitOutcome::Unknown
+        );
+    }
+
+    #[test]
+    fn rules_out_known_wrong_answers() {
+        let guess = |answer: &str, outcome| WrongGuess {
+            part: 1,
+            answer: answer.into(),
+            outcome,
+        };
+
+        assert_eq!(guess("42", SubmitOutcome::Wrong).rules_out("42"), true);
+        assert_eq!(guess("42", SubmitOutcome::Wrong).rules_out("43"), false);
+        assert_eq!(guess("42", SubmitOutcome::TooHigh).rules_out("50"), true);
+        assert_eq!(guess("42", SubmitOutcome::TooHigh).rules_out("41"), false);
+        assert_eq!(guess("42", SubmitOutcome::TooLow).rules_out("-3"), true);
+        assert_eq!(guess("42", SubmitOutcome::TooLow).rules_out("abc"), false);
+    }
+
+    #[test]
+    fn parses_log_lines() {
+        assert_eq!(
+            parse_log_line("2023-12-01 05:00:00\t2\ttoo low\t1 2 3"),
+            Some(WrongGuess {
+                part: 2,
+                answer: "1 2 3".into(),
+                outcome: SubmitOutcome::TooLow,
+            })
+        );
+        assert_eq!(parse_log_line("garbage"), None);
+    }
+
+    #[test]
+    fn records_submissions() {
+        let dir = env::temp_dir().join(format!("aoc-submission-{}", std::process::id()));
+        let year = Year::primary().unwrap();
+
+        let backend = RecordingBackend::new(
+            "",
+            "",
+            "That's not the right answer; your answer is too high.",
+        );
+        let (outcome, _) = submit_answer(&backend, &dir, year, day!(1), 1, "100").unwrap();
+        assert_eq!(outcome, SubmitOutcome::TooHigh);
+
+        // guesses beyond a guess that was too high are refused without calling the backend.
+        assert!(matches!(
+            submit_answer(&backend, &dir, year, day!(1), 1, "120"),
+            Err(SubmitError::RuledOut(_))
+        ));
+        assert_eq!(check_answer(&dir, day!(1), 1, "99").is_ok(), true);
+        assert_eq!(check_answer(&dir, day!(1), 2, "120").is_ok(), true);
+
+        let backend = RecordingBackend::new("", "", "That's the right answer!");
+        let (outcome, _) = submit_answer(&backend, &dir, year, day!(1), 1, "42").unwrap();
+        assert_eq!(outcome, SubmitOutcome::Correct);
+        assert_eq!(Answers::read_in(&dir, day!(1)).get(1), Some("42"));
+
+        assert!(matches!(
+            submit_answer(&backend, &dir, year, day!(1), 1, "42"),
+            Err(SubmitError::AlreadyAccepted)
+        ));
+        assert!(matches!(
+            submit_answer(&backend, &dir, year, day!(1), 1, "43"),
+            Err(SubmitError::DiffersFromKnownAnswer)
+        ));
+        assert_eq!(
+            backend.calls(),
+            vec![BackendCall::Submit(year, day!(1), 1, "42".into())]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,9 +1,12 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::runner::{PartResult, PARSE_STAGE};
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::{Day, Year, MAX_DAYS};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+static BASELINES_DIR_NAME: &str = "baselines";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +15,63 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Time spent in the shared parse stage, for solutions that declare one.
+    pub parse: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    /// Heap usage of each stage, if it was recorded with `cargo time --memory`.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+    pub parse_memory: Option<MemoryStats>,
+}
+
+impl Timing {
+    /// Mean duration of a part in nanoseconds, if it was benched. Part [`PARSE_STAGE`] is the parse stage.
+    pub fn mean_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            PARSE_STAGE => self.parse.as_deref().and_then(parse_duration_nanos),
+            1 => self.part_1.as_deref().and_then(parse_duration_nanos),
+            2 => self.part_2.as_deref().and_then(parse_duration_nanos),
+            _ => None,
+        }
+    }
+
+    /// Benchmark statistics of a part, if they were stored.
+    pub fn stats(&self, part: u8) -> Option<&BenchStats> {
+        match part {
+            PARSE_STAGE => self.parse_stats.as_ref(),
+            1 => self.part_1_stats.as_ref(),
+            2 => self.part_2_stats.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Heap usage of a part, if it was recorded.
+    pub fn memory(&self, part: u8) -> Option<&MemoryStats> {
+        match part {
+            PARSE_STAGE => self.parse_memory.as_ref(),
+            1 => self.part_1_memory.as_ref(),
+            2 => self.part_2_memory.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a duration as printed by its `Debug` implementation, e.g. `74.13µs`, into nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.trim().strip_suffix(postfix)?.parse::<f64>().ok();
+
+    if s.ends_with("ns") {
+        parse_to_float("ns")
+    } else if s.ends_with("µs") {
+        parse_to_float("µs").map(|x| x * 1000_f64)
+    } else if s.ends_with("ms") {
+        parse_to_float("ms").map(|x| x * 1_000_000_f64)
+    } else {
+        parse_to_float("s").map(|x| x * 1_000_000_000_f64)
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,16 +82,87 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Collect the timings of a set of results. Only benched parts carry a timing.
+    pub fn from_results(results: &[PartResult]) -> Self {
+        let mut data: Vec<Timing> = vec![];
+
+        for result in results {
+            let index = match data.iter().position(|t| t.day == result.day) {
+                Some(index) => index,
+                None => {
+                    data.push(Timing {
+                        day: result.day,
+                        part_1: None,
+                        part_2: None,
+                        total_nanos: 0_f64,
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        parse: None,
+                        parse_stats: None,
+                        part_1_memory: None,
+                        part_2_memory: None,
+                        parse_memory: None,
+                    });
+                    data.len() - 1
+                }
+            };
+
+            let timing = &mut data[index];
+
+            // NOTE: memory is recorded in a separate run, without benching.
+            match result.part {
+                PARSE_STAGE => timing.parse_memory = timing.parse_memory.or(result.memory),
+                1 => timing.part_1_memory = timing.part_1_memory.or(result.memory),
+                2 => timing.part_2_memory = timing.part_2_memory.or(result.memory),
+                _ => {}
+            }
+
+            if result.samples <= 1 {
+                continue;
+            }
+
+            let duration_str = format!("{:.1?}", result.duration);
+            match result.part {
+                PARSE_STAGE => {
+                    timing.parse = Some(duration_str);
+                    timing.parse_stats = result.stats;
+                }
+                1 => {
+                    timing.part_1 = Some(duration_str);
+                    timing.part_1_stats = result.stats;
+                }
+                2 => {
+                    timing.part_2 = Some(duration_str);
+                    timing.part_2_stats = result.stats;
+                }
+                _ => continue,
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = result.duration.as_nanos() as f64;
+            timing.total_nanos += nanos;
+        }
+
+        data.sort_unstable_by_key(|t| t.day);
+        Timings { data }
+    }
+
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        fs::create_dir_all(year.data_dir())?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(year.data_dir().join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let path = year.data_dir().join(TIMINGS_FILE_NAME);
+        if !path.exists() {
+            return Timings::default();
+        }
+
+        let s = fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -44,6 +175,22 @@ impl Timings {
         }
     }
 
+    /// Dehydrate timings to the JSON file of a named baseline.
+    pub fn store_baseline(&self, year: Year, name: &str) -> Result<(), Error> {
+        fs::create_dir_all(year.data_dir().join(BASELINES_DIR_NAME))?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_baseline_path(year, name))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate timings from the JSON file of a named baseline.
+    /// Unlike [`Timings::read_from_file`], a missing baseline is an error.
+    pub fn read_baseline(year: Year, name: &str) -> Result<Self, String> {
+        fs::read_to_string(get_baseline_path(year, name))
+            .map_err(|e| format!("could not read baseline `{name}`: {e}"))
+            .and_then(Timings::try_from)
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
@@ -58,7 +205,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -74,6 +221,21 @@ impl Timings {
     }
 }
 
+/// Whether a baseline name is safe to use as a file name.
+pub fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.')
+}
+
+fn get_baseline_path(year: Year, name: &str) -> PathBuf {
+    year.data_dir()
+        .join(BASELINES_DIR_NAME)
+        .join(format!("{name}.json"))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -94,7 +256,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -140,6 +309,42 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match &value.parse {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+            ("parse_stats", &value.parse_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        for (key, memory) in [
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+            ("parse_memory", &value.parse_memory),
+        ] {
+            map.insert(
+                key.into(),
+                match memory {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -155,7 +360,10 @@ impl TryFrom<&JsonValue> for Timing {
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
+            // timings do not know their year, so accept any day that an event can have.
+            .and_then(|day| day.parse::<u8>().ok())
+            .filter(|day| (1..=MAX_DAYS).contains(day))
+            .map(Day::__new_unchecked)
             .ok_or("Expected timing.day to be a Day struct.")?;
 
         let part_1 = json
@@ -173,11 +381,31 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: statistics are optional, timings stored by older versions do not have them.
+        let get_stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => BenchStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        // NOTE: memory is optional, it is only recorded with `cargo time --memory`.
+        let get_memory = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => MemoryStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_stats: get_stats("part_1_stats")?,
+            part_2_stats: get_stats("part_2_stats")?,
+            // NOTE: the parse stage is optional, only solutions that declare one have it.
+            parse: json.get("parse").and_then(|v| v.get::<String>()).cloned(),
+            parse_stats: get_stats("parse_stats")?,
+            part_1_memory: get_memory("part_1_memory")?,
+            part_2_memory: get_memory("part_2_memory")?,
+            parse_memory: get_memory("parse_memory")?,
         })
     }
 }
@@ -198,18 +426,39 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 },
             ],
         }
@@ -228,6 +477,25 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "warmup": 10, "mean_nanos": 1000000, "median_nanos": 900000, "p5_nanos": 800000, "p95_nanos": 1200000, "std_dev_nanos": 100000, "min_nanos": 700000, "max_nanos": 1500000, "outliers": { "low_severe": 0, "low_mild": 0, "high_mild": 2, "high_severe": 1 } }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let stats = timings.data[0].part_1_stats.unwrap();
+            assert_eq!(stats.median.as_nanos(), 900_000);
+            assert_eq!(stats.outliers.total(), 3);
+            assert_eq!(timings.data[0].part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_parse_stage() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1500000, "parse": "500µs" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].parse, Some("500µs".into()));
+            assert_eq!(timings.data[0].parse_stats, None);
         }
 
         #[test]
@@ -289,10 +557,17 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +578,17 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,17 +599,58 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod parse_duration_nanos {
+        use crate::template::timings::parse_duration_nanos;
+
+        #[test]
+        fn parses_duration_units() {
+            assert_eq!(parse_duration_nanos("74.5ns"), Some(74.5));
+            assert_eq!(parse_duration_nanos("74.5µs"), Some(74_500.0));
+            assert_eq!(parse_duration_nanos("74.5ms"), Some(74_500_000.0));
+            assert_eq!(parse_duration_nanos("2s"), Some(2_000_000_000.0));
+        }
+
+        #[test]
+        fn handles_invalid_durations() {
+            assert_eq!(parse_duration_nanos("-"), None);
+            assert_eq!(parse_duration_nanos("fast"), None);
+        }
+    }
+
+    mod baselines {
+        use crate::template::timings::is_valid_baseline_name;
+
+        #[test]
+        fn validates_baseline_names() {
+            assert_eq!(is_valid_baseline_name("main"), true);
+            assert_eq!(is_valid_baseline_name("feature-1.2_b"), true);
+            assert_eq!(is_valid_baseline_name(""), false);
+            assert_eq!(is_valid_baseline_name(".."), false);
+            assert_eq!(is_valid_baseline_name("../main"), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                runner::PARSE_STAGE,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -341,6 +664,13 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +691,13 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: None,
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +709,36 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn handles_timings_with_parse_stage() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 1_500_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse: Some("500µs".into()),
+                    parse_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_memory: None,
+                }],
+            };
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].mean_nanos(PARSE_STAGE), Some(500_000_f64));
+
+            // timings of other days leave the parse stage in place.
+            let merged = merged.merge(&Timings {
+                data: vec![get_mock_timings().data[0].clone()],
+            });
+            assert_eq!(merged.data[1].parse, Some("500µs".into()));
+            assert_eq!(merged.data[1].total_nanos, 1_500_000_f64);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -388,4 +755,87 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod from_results {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                answers::Verdict,
+                runner::{PartResult, PARSE_STAGE},
+                stats::{BenchStats, MemoryStats},
+                timings::Timings,
+            },
+        };
+
+        fn result(day: u8, part: u8, nanos: u64, samples: u128) -> PartResult {
+            PartResult {
+                year: crate::year!(2023),
+                day: crate::template::Day::new(day).unwrap(),
+                part,
+                answer: Some("0".into()),
+                duration: Duration::from_nanos(nanos),
+                samples,
+                stats: (samples > 1).then(|| BenchStats {
+                    mean: Duration::from_nanos(nanos),
+                    ..BenchStats::default()
+                }),
+                memory: None,
+                verdict: Verdict::Unknown,
+                failure: None,
+            }
+        }
+
+        #[test]
+        fn converts_benched_results() {
+            let timings = Timings::from_results(&[
+                result(2, 1, 74, 100_000),
+                result(2, 2, 2_000_000, 10),
+                result(1, 1, 1_000, 10),
+            ]);
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(timings.data[0].day, day!(1));
+            assert_eq!(timings.data[1].total_nanos, 2_000_074_f64);
+            assert_eq!(timings.data[1].part_1, Some("74.0ns".into()));
+            assert_eq!(timings.data[1].part_2, Some("2.0ms".into()));
+            assert_eq!(
+                timings.data[1].part_1_stats.unwrap().mean,
+                Duration::from_nanos(74)
+            );
+        }
+
+        #[test]
+        fn converts_parse_stages() {
+            let timings =
+                Timings::from_results(&[result(1, PARSE_STAGE, 500, 10), result(1, 1, 1_000, 10)]);
+            assert_eq!(timings.data[0].parse, Some("500.0ns".into()));
+            assert_eq!(timings.data[0].total_nanos, 1_500_f64);
+            assert_eq!(timings.data[0].mean_nanos(PARSE_STAGE), Some(500_f64));
+        }
+
+        #[test]
+        fn keeps_memory_of_unbenched_results() {
+            let memory = MemoryStats {
+                peak_bytes: 1024,
+                total_allocations: 2,
+                total_bytes: 2048,
+            };
+            let timings = Timings::from_results(&[PartResult {
+                memory: Some(memory),
+                ..result(1, 2, 74, 1)
+            }]);
+            assert_eq!(timings.data[0].part_2, None);
+            assert_eq!(timings.data[0].memory(2), Some(&memory));
+            assert_eq!(timings.data[0].memory(1), None);
+        }
+
+        #[test]
+        fn skips_unbenched_results() {
+            let timings = Timings::from_results(&[result(1, 1, 74, 1)]);
+            assert_eq!(timings.data.len(), 1);
+            assert_eq!(timings.data[0].total_nanos, 0_f64);
+            assert_eq!(timings.data[0].part_1.is_none(), true);
+        }
+    }
 }
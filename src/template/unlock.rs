@@ -0,0 +1,200 @@
+/// Module that knows when puzzles unlock, using a clock that can be replaced in tests.
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{all_days_in, Day, Year};
+
+/// Puzzles unlock at midnight in the timezone of the Advent of Code server, UTC-5.
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Block for `duration`. Clocks used in tests advance their time instead.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).expect("valid offset")
+}
+
+/// The moment the puzzle of a day unlocks, i.e. midnight of that day in december, server time.
+pub fn unlock_time(year: Year, day: Day) -> Option<DateTime<Utc>> {
+    server_offset()
+        .with_ymd_and_hms(
+            i32::from(year.into_inner()),
+            12,
+            u32::from(day.into_inner()),
+            0,
+            0,
+            0,
+        )
+        .single()
+        .map(|x| x.with_timezone(&Utc))
+}
+
+/// The next day of the event of `year` whose puzzle is still locked, and when it unlocks.
+/// Returns `None` once every puzzle of the event is unlocked.
+pub fn next_unlock(clock: &dyn Clock, year: Year) -> Option<(Day, DateTime<Utc>)> {
+    let now = clock.now();
+
+    all_days_in(year)
+        .filter_map(|day| Some((day, unlock_time(year, day)?)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+/// The current day if it's a december day of the event of `year` in server time, `None` otherwise.
+/// Only the month is checked, so that the puzzles of past years can be done on the same days.
+pub fn current_day(clock: &dyn Clock, year: Year) -> Option<Day> {
+    let today = clock.now().with_timezone(&server_offset());
+    if today.month() == 12 {
+        Day::new_in(year, u8::try_from(today.day()).ok()?)
+    } else {
+        None
+    }
+}
+
+/// Sleep until `time`, calling `on_tick` with the remaining time about once a second.
+pub fn wait_until(clock: &dyn Clock, time: DateTime<Utc>, mut on_tick: impl FnMut(Duration)) {
+    loop {
+        let Ok(remaining) = (time - clock.now()).to_std() else {
+            return;
+        };
+
+        if remaining.is_zero() {
+            return;
+        }
+
+        on_tick(remaining);
+
+        // sleep until the next full second, so the countdown ticks evenly.
+        let fraction = remaining.subsec_nanos();
+        let tick = if fraction == 0 {
+            Duration::from_secs(1)
+        } else {
+            Duration::from_nanos(u64::from(fraction))
+        };
+        clock.sleep(tick.min(remaining));
+    }
+}
+
+/// Format the time left until an unlock, e.g. `2d 03h 04m 05s` or `04m 05s`.
+pub fn format_countdown(duration: Duration) -> String {
+    // round up, so that the countdown does not show zero while the puzzle is still locked.
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}h {minutes:02}m {seconds:02}s")
+    } else if hours > 0 {
+        format!("{hours}h {minutes:02}m {seconds:02}s")
+    } else {
+        format!("{minutes:02}m {seconds:02}s")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{current_day, format_countdown, next_unlock, unlock_time, wait_until, Clock};
+    use crate::{day, year};
+
+    /// A clock that stands still, and only advances when slept on.
+    struct FixedClock(Cell<DateTime<Utc>>);
+
+    impl FixedClock {
+        fn at(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Self {
+            FixedClock(Cell::new(
+                Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+                    .unwrap(),
+            ))
+        }
+    }
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration);
+        }
+    }
+
+    #[test]
+    fn unlocks_at_midnight_server_time() {
+        assert_eq!(
+            unlock_time(year!(2023), day!(1)),
+            Some(Utc.with_ymd_and_hms(2023, 12, 1, 5, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let before = FixedClock::at(2024, 11, 30, 12, 0, 0);
+        assert_eq!(
+            next_unlock(&before, year!(2024)),
+            Some((day!(1), Utc.with_ymd_and_hms(2024, 12, 1, 5, 0, 0).unwrap()))
+        );
+
+        // at 04:59 UTC on the 5th it is still the 4th on the server.
+        let during = FixedClock::at(2024, 12, 5, 4, 59, 0);
+        assert_eq!(
+            next_unlock(&during, year!(2024)).map(|x| x.0),
+            Some(day!(5))
+        );
+        assert_eq!(current_day(&during, year!(2024)), Some(day!(4)));
+
+        let after = FixedClock::at(2025, 12, 12, 5, 0, 0);
+        assert_eq!(next_unlock(&after, year!(2025)), None);
+        assert_eq!(current_day(&after, year!(2025)), Some(day!(12)));
+
+        let outside = FixedClock::at(2025, 12, 13, 12, 0, 0);
+        assert_eq!(current_day(&outside, year!(2025)), None);
+    }
+
+    #[test]
+    fn waits_with_countdown() {
+        let clock = FixedClock::at(2024, 12, 1, 4, 59, 57);
+        let mut ticks = vec![];
+
+        let unlock = unlock_time(year!(2024), day!(1)).unwrap();
+        wait_until(&clock, unlock, |x| ticks.push(format_countdown(x)));
+
+        assert_eq!(clock.now(), unlock);
+        assert_eq!(ticks, vec!["00m 03s", "00m 02s", "00m 01s"]);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(59_500)), "01m 00s");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "1h 02m 03s");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86_400 + 5)),
+            "2d 00h 00m 05s"
+        );
+    }
+}
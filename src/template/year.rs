@@ -0,0 +1,300 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::template::{Day, MAX_DAYS};
+
+// NOTE: both are read at compile time, so that `day!` can check day numbers in a const context, and binaries
+// run outside of cargo agree with the ones run through it.
+const PRIMARY_YEAR: Option<&str> = option_env!("AOC_YEAR");
+const PRIMARY_DAYS: Option<&str> = option_env!("AOC_DAYS");
+
+/// A year of advent, e.g. `2023`. The first event took place in 2015.
+///
+/// The primary year of a repository, set via `AOC_YEAR` in `.cargo/config.toml`, uses the top-level
+/// `data` folder and `src/bin/<day>.rs`. Every other year is scoped to `data/<year>` and `src/bin/<year>-<day>.rs`.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2022).unwrap();
+/// assert_eq!(year.to_string(), "2022")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent, returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __parse_const(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        assert!(
+            bytes.len() == 4,
+            "expecting AOC_YEAR to be a four digit year"
+        );
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "expecting AOC_YEAR to be a four digit year"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= 2015, "expecting AOC_YEAR to be 2015 or later");
+        Self(year)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __days_const(self) -> u8 {
+        days_of_event(self, PRIMARY_YEAR, PRIMARY_DAYS)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __primary_days_const() -> u8 {
+        match PRIMARY_YEAR {
+            Some(primary) => Self::__parse_const(primary).__days_const(),
+            None => 25,
+        }
+    }
+
+    /// The number of puzzles of this year's event. Events up to 2024 have 25 puzzles, later ones 12.
+    /// For the primary year, this can be changed with `AOC_DAYS` in `.cargo/config.toml`, e.g. for events of your own.
+    pub fn days(self) -> u8 {
+        self.__days_const()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The primary year of the repository, set in `AOC_YEAR` when it was compiled.
+    /// This is the same source that the number of days of the primary year is read from, see [`Year::days`].
+    pub fn primary() -> Option<Self> {
+        PRIMARY_YEAR?.parse().ok()
+    }
+
+    pub fn is_primary(self) -> bool {
+        Self::primary() == Some(self)
+    }
+
+    /// Scope a folder to this year, e.g. `data` to `data/2022`. The primary year is not scoped.
+    pub fn scope(self, root: &Path) -> PathBuf {
+        if self.is_primary() {
+            root.to_path_buf()
+        } else {
+            root.join(self.to_string())
+        }
+    }
+
+    /// The folder that holds inputs, examples, puzzles, answers and timings of this year.
+    pub fn data_dir(self) -> PathBuf {
+        self.scope(Path::new("data"))
+    }
+
+    /// Name of the binary that holds the solution of a day, e.g. `01` or `2022-01`.
+    pub fn bin_name(self, day: Day) -> String {
+        if self.is_primary() {
+            day.to_string()
+        } else {
+            format!("{self}-{day}")
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, e.g. 2023")
+    }
+}
+
+/// The number of puzzles of the event of `year`, given the primary year and its number of puzzles in `AOC_DAYS`.
+const fn days_of_event(year: Year, primary: Option<&str>, days: Option<&str>) -> u8 {
+    if let (Some(primary), Some(days)) = (primary, days) {
+        if Year::__parse_const(primary).0 == year.0 {
+            return parse_days_const(days);
+        }
+    }
+
+    if year.0 < 2025 {
+        25
+    } else {
+        12
+    }
+}
+
+/// Parse the number of puzzles set in `AOC_DAYS` at compile time.
+const fn parse_days_const(s: &str) -> u8 {
+    let bytes = s.as_bytes();
+    assert!(
+        !bytes.is_empty() && bytes.len() <= 2,
+        "expecting AOC_DAYS to be a number between 1 and 99"
+    );
+
+    let mut days = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "expecting AOC_DAYS to be a number between 1 and 99"
+        );
+        days = days * 10 + (bytes[i] - b'0');
+        i += 1;
+    }
+
+    assert!(
+        days >= 1 && days <= MAX_DAYS,
+        "expecting AOC_DAYS to be a number between 1 and 99"
+    );
+    days
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Every year that has solutions in this repository, in ascending order.
+/// This is the primary year plus every year with a `src/bin/<year>-<day>.rs` binary or a `data/<year>` folder.
+pub fn all_years() -> Vec<Year> {
+    let mut years: Vec<Year> = Year::primary().into_iter().collect();
+
+    let bins = fs::read_dir("src/bin").into_iter().flatten().flatten();
+    let data = fs::read_dir("data").into_iter().flatten().flatten();
+
+    for entry in bins.chain(data) {
+        let name = entry.file_name();
+        let Some(year) = name
+            .to_str()
+            .and_then(|x| x.get(..4))
+            .and_then(|x| x.parse::<Year>().ok())
+        else {
+            continue;
+        };
+
+        if !years.contains(&year) {
+            years.push(year);
+        }
+    }
+
+    years.sort_unstable();
+    years
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, e.g. 2023"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{days_of_event, parse_days_const, Year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().unwrap(), Year(2022));
+        assert_eq!("2014".parse::<Year>().is_err(), true);
+        assert_eq!("22".parse::<Year>().is_err(), true);
+        assert_eq!(Year::__parse_const("2023"), Year(2023));
+    }
+
+    #[test]
+    fn counts_days_of_events() {
+        assert_eq!(Year(2015).days(), 25);
+        assert_eq!(Year(2024).days(), 25);
+        assert_eq!(Year(2025).days(), 12);
+        assert_eq!(parse_days_const("7"), 7);
+        assert_eq!(parse_days_const("31"), 31);
+    }
+
+    #[test]
+    fn overrides_days_of_primary_year() {
+        assert_eq!(days_of_event(Year(2030), Some("2030"), Some("10")), 10);
+        assert_eq!(days_of_event(Year(2023), Some("2030"), Some("10")), 25);
+        assert_eq!(days_of_event(Year(2030), Some("2030"), None), 12);
+        assert_eq!(days_of_event(Year(2030), None, Some("10")), 12);
+
+        // the primary year and its days are read from the same place.
+        let primary = Year::primary().unwrap();
+        assert_eq!(
+            primary.days(),
+            days_of_event(primary, Some(&primary.to_string()), option_env!("AOC_DAYS"))
+        );
+        assert_eq!(Year::__primary_days_const(), primary.days());
+        assert_eq!(
+            crate::template::all_days().count(),
+            usize::from(primary.days())
+        );
+    }
+
+    #[test]
+    fn scopes_paths_to_years() {
+        // NOTE: `AOC_YEAR` is set to the primary year via `.cargo/config.toml`.
+        let primary = Year::primary().unwrap();
+        let other = Year::new(primary.into_inner() - 1).unwrap();
+
+        assert_eq!(primary.data_dir().to_str(), Some("data"));
+        assert_eq!(primary.bin_name(crate::day!(1)), "01");
+        assert_eq!(
+            other.data_dir(),
+            std::path::Path::new("data").join(other.to_string())
+        );
+        assert_eq!(other.bin_name(crate::day!(1)), format!("{other}-01"));
+    }
+}